@@ -1,11 +1,10 @@
 //! Functions for manipulating the contrast of images.
 
 use crate::definitions::{HasBlack, HasWhite};
-use crate::integral_image::{integral_image, sum_image_pixels};
+use crate::integral_image::{integral_image, integral_squared_image, sum_image_pixels};
+use crate::map::{apply_lut, apply_lut_mut};
 use crate::stats::{cumulative_histogram, histogram};
-use image::{GrayImage, ImageBuffer, Luma};
-#[cfg(feature = "rayon")]
-use rayon::prelude::*;
+use image::{GenericImageView, GrayImage, ImageBuffer, Luma};
 use std::cmp::{max, min};
 
 /// Applies an adaptive threshold to an image.
@@ -13,7 +12,30 @@ use std::cmp::{max, min};
 /// This algorithm compares each pixel's brightness with the average brightness of the pixels
 /// in the (2 * `block_radius` + 1) square block centered on it. If the pixel is at least as bright
 /// as the threshold then it will have a value of 255 in the output image, otherwise 0.
+///
+/// This is equivalent to [`adaptive_threshold_with_offset`] with an offset of 0.
+///
+/// [`adaptive_threshold_with_offset`]: fn.adaptive_threshold_with_offset.html
 pub fn adaptive_threshold(image: &GrayImage, block_radius: u32) -> GrayImage {
+    adaptive_threshold_with_offset(image, block_radius, 0)
+}
+
+/// Applies an adaptive threshold to an image, using the local mean minus `c` as the threshold.
+///
+/// Each pixel is compared with the average brightness of the pixels in the
+/// (2 * `block_radius` + 1) square block centered on it, rounded down. If the pixel is at least
+/// as bright as this mean minus `c` then it will have a value of 255 in the output image,
+/// otherwise 0. Positive values of `c` classify more pixels as foreground, which
+/// prevents noise in flat regions, such as the background of a scanned document, from being
+/// split between black and white.
+///
+/// The local means are computed using an integral image, so the running time does not
+/// depend on `block_radius`.
+///
+/// # Panics
+///
+/// If `block_radius` is 0.
+pub fn adaptive_threshold_with_offset(image: &GrayImage, block_radius: u32, c: i32) -> GrayImage {
     assert!(block_radius > 0);
     let integral = integral_image::<_, u32>(image);
     let mut out = ImageBuffer::from_pixel(image.width(), image.height(), Luma::black());
@@ -35,7 +57,63 @@ pub fn adaptive_threshold(image: &GrayImage, block_radius: u32) -> GrayImage {
             let w = (y_high - y_low + 1) * (x_high - x_low + 1);
             let mean = sum_image_pixels(&integral, x_low, y_low, x_high, y_high)[0] / w;
 
-            if current_pixel[0] as u32 >= mean as u32 {
+            if current_pixel[0] as i64 >= mean as i64 - c as i64 {
+                out.put_pixel(x, y, Luma::white());
+            }
+        }
+    }
+    out
+}
+
+/// Applies [Sauvola's] adaptive threshold to an image.
+///
+/// Each pixel is compared with a threshold computed from the mean `m` and standard deviation
+/// `s` of the pixels in the (2 * `block_radius` + 1) square block centered on it:
+/// `m * (1 + k * (s / r - 1))`. If the pixel is at least as bright as this threshold then it
+/// will have a value of 255 in the output image, otherwise 0.
+///
+/// `r` is the dynamic range of the standard deviation, and is typically 128 for 8bpp images.
+/// `k` is typically between 0.2 and 0.5, with larger values producing thinner foreground
+/// regions. Compared with [`adaptive_threshold`], the threshold drops in low contrast regions,
+/// which makes this method more robust on faded or unevenly lit documents.
+///
+/// The local statistics are computed using integral images, so the running time does not
+/// depend on `block_radius`.
+///
+/// # Panics
+///
+/// If `block_radius` is 0.
+///
+/// [Sauvola's]: https://doi.org/10.1016/S0031-3203(99)00055-2
+/// [`adaptive_threshold`]: fn.adaptive_threshold.html
+pub fn sauvola_threshold(image: &GrayImage, block_radius: u32, k: f64, r: f64) -> GrayImage {
+    assert!(block_radius > 0);
+    let integral = integral_image::<_, u64>(image);
+    let integral_squared = integral_squared_image::<_, u64>(image);
+    let mut out = ImageBuffer::from_pixel(image.width(), image.height(), Luma::black());
+
+    for y in 0..image.height() {
+        for x in 0..image.width() {
+            let (y_low, y_high) = (
+                y.saturating_sub(block_radius),
+                min(image.height() - 1, y + block_radius),
+            );
+            let (x_low, x_high) = (
+                x.saturating_sub(block_radius),
+                min(image.width() - 1, x + block_radius),
+            );
+
+            // Number of pixels in the block, adjusted for edge cases.
+            let n = ((y_high - y_low + 1) * (x_high - x_low + 1)) as f64;
+            let sum = sum_image_pixels(&integral, x_low, y_low, x_high, y_high)[0] as f64;
+            let sum_sq =
+                sum_image_pixels(&integral_squared, x_low, y_low, x_high, y_high)[0] as f64;
+
+            let mean = sum / n;
+            let variance = (sum_sq / n - mean * mean).max(0.0);
+            let threshold = mean * (1.0 + k * (variance.sqrt() / r - 1.0));
+
+            if image.get_pixel(x, y)[0] as f64 >= threshold {
                 out.put_pixel(x, y, Luma::white());
             }
         }
@@ -99,6 +177,35 @@ pub fn otsu_level(image: &GrayImage) -> u8 {
     best_threshold
 }
 
+/// Returns a binarized image from an input 8bpp grayscale image, obtained by
+/// applying the threshold computed by [`otsu_level`]. Pixels with intensity
+/// equal to the threshold are assigned to the background.
+///
+/// # Examples
+/// ```
+/// # extern crate image;
+/// # #[macro_use]
+/// # extern crate imageproc;
+/// # fn main() {
+/// use imageproc::contrast::otsu_threshold;
+///
+/// let image = gray_image!(
+///     10, 80, 20;
+///     15, 90, 85);
+///
+/// let thresholded = gray_image!(
+///     0, 255,   0;
+///     0, 255, 255);
+///
+/// assert_pixels_eq!(otsu_threshold(&image), thresholded);
+/// # }
+/// ```
+///
+/// [`otsu_level`]: fn.otsu_level.html
+pub fn otsu_threshold(image: &GrayImage) -> GrayImage {
+    threshold(image, otsu_level(image))
+}
+
 /// Returns a binarized image from an input 8bpp grayscale image
 /// obtained by applying the given threshold. Pixels with intensity
 /// equal to the threshold are assigned to the background.
@@ -162,32 +269,40 @@ pub fn threshold_mut(image: &mut GrayImage, thresh: u8) {
 /// Equalises the histogram of an 8bpp grayscale image in place. See also
 /// [histogram equalization (wikipedia)](https://en.wikipedia.org/wiki/Histogram_equalization).
 pub fn equalize_histogram_mut(image: &mut GrayImage) {
-    let hist = cumulative_histogram(image).channels[0];
-    let total = hist[255] as f32;
-
-    #[cfg(feature = "rayon")]
-    let iter = image.par_iter_mut();
-    #[cfg(not(feature = "rayon"))]
-    let iter = image.iter_mut();
-
-    iter.for_each(|p| {
-        // JUSTIFICATION
-        //  Benefit
-        //      Using checked indexing here makes this function take 1.1x longer, as measured
-        //      by bench_equalize_histogram_mut
-        //  Correctness
-        //      Each channel of CumulativeChannelHistogram has length 256, and a GrayImage has 8 bits per pixel
-        let fraction = unsafe { *hist.get_unchecked(*p as usize) as f32 / total };
-        *p = (f32::min(255f32, 255f32 * fraction)) as u8;
-    });
+    let lut = equalization_lut(&cumulative_histogram(image).channels[0]);
+    apply_lut_mut(image, &lut);
 }
 
 /// Equalises the histogram of an 8bpp grayscale image. See also
 /// [histogram equalization (wikipedia)](https://en.wikipedia.org/wiki/Histogram_equalization).
-pub fn equalize_histogram(image: &GrayImage) -> GrayImage {
-    let mut out = image.clone();
-    equalize_histogram_mut(&mut out);
-    out
+///
+/// `image` may be any view with `Luma<u8>` pixels, for example a sub-image. The result is
+/// identical to copying `image` and calling [`equalize_histogram_mut`] on the copy.
+///
+/// [`equalize_histogram_mut`]: fn.equalize_histogram_mut.html
+pub fn equalize_histogram<I>(image: &I) -> GrayImage
+where
+    I: GenericImageView<Pixel = Luma<u8>>,
+{
+    let mut hist = [0u32; 256];
+    for (_, _, p) in image.pixels() {
+        hist[p[0] as usize] += 1;
+    }
+    for i in 1..hist.len() {
+        hist[i] += hist[i - 1];
+    }
+    apply_lut(image, &equalization_lut(&hist))
+}
+
+/// Maps each intensity to its equalised value, given the cumulative histogram of an image.
+fn equalization_lut(hist: &[u32; 256]) -> [u8; 256] {
+    let total = hist[255] as f32;
+    let mut lut = [0u8; 256];
+    for (l, &h) in lut.iter_mut().zip(hist.iter()) {
+        let fraction = h as f32 / total;
+        *l = (f32::min(255f32, 255f32 * fraction)) as u8;
+    }
+    lut
 }
 
 /// Adjusts contrast of an 8bpp grayscale image in place so that its
@@ -310,6 +425,55 @@ mod tests {
     use image::{GrayImage, Luma};
     use test::{black_box, Bencher};
 
+    #[test]
+    fn adaptive_threshold_with_offset_noisy_background() {
+        let image = gray_image!(
+            200, 198, 201, 199;
+            202, 100, 200, 197;
+            199, 201, 203, 200);
+
+        let plain = gray_image!(
+            255, 255, 255, 255;
+            255,   0, 255,   0;
+            255, 255, 255, 255);
+        assert_pixels_eq!(adaptive_threshold_with_offset(&image, 1, 0), plain);
+        assert_pixels_eq!(adaptive_threshold(&image, 1), plain);
+
+        let offset = gray_image!(
+            255, 255, 255, 255;
+            255,   0, 255, 255;
+            255, 255, 255, 255);
+        assert_pixels_eq!(adaptive_threshold_with_offset(&image, 1, 5), offset);
+
+        let negative = gray_image!(
+              0,   0,   0,   0;
+              0,   0,   0,   0;
+              0,   0,   0,   0);
+        assert_pixels_eq!(adaptive_threshold_with_offset(&image, 1, -60), negative);
+    }
+
+    #[test]
+    fn sauvola_threshold_faded_text() {
+        // Dark text on a light background, with the right half faded.
+        let image = gray_image!(
+            200, 200, 200, 180, 180, 180;
+            200,  40, 200, 180, 130, 180;
+            200, 200, 200, 180, 180, 180);
+
+        let expected = gray_image!(
+            255, 255, 255, 255, 255, 255;
+            255,   0, 255, 255,   0, 255;
+            255, 255, 255, 255, 255, 255);
+        assert_pixels_eq!(sauvola_threshold(&image, 1, 0.2, 128.0), expected);
+    }
+
+    #[test]
+    fn sauvola_threshold_constant() {
+        let image = GrayImage::from_pixel(4, 4, Luma([100u8]));
+        let expected = GrayImage::from_pixel(4, 4, Luma::white());
+        assert_pixels_eq!(sauvola_threshold(&image, 2, 0.5, 128.0), expected);
+    }
+
     #[test]
     fn adaptive_threshold_constant() {
         let image = GrayImage::from_pixel(3, 3, Luma([100u8]));
@@ -508,6 +672,31 @@ mod tests {
         assert_pixels_eq!(expected, actual);
     }
 
+    #[test]
+    fn test_equalize_histogram_matches_equalize_histogram_mut() {
+        let image = gray_bench_image(37, 23);
+        let mut expected = image.clone();
+        equalize_histogram_mut(&mut expected);
+        assert_pixels_eq!(equalize_histogram(&image), expected);
+    }
+
+    #[test]
+    fn test_equalize_histogram_matches_equalize_histogram_mut_on_large_image() {
+        let image = GrayImage::from_fn(4, 131_071, |x, y| Luma([((x + 7 * y) % 256) as u8]));
+        let mut expected = image.clone();
+        equalize_histogram_mut(&mut expected);
+        assert_eq!(equalize_histogram(&image), expected);
+    }
+
+    #[test]
+    fn test_equalize_histogram_sub_image() {
+        let mut image = gray_bench_image(20, 20);
+        let mut crop = image::imageops::crop(&mut image, 5, 5, 10, 10).to_image();
+        let equalized = equalize_histogram(&image::imageops::crop(&mut image, 5, 5, 10, 10));
+        equalize_histogram_mut(&mut crop);
+        assert_pixels_eq!(equalized, crop);
+    }
+
     #[bench]
     fn bench_equalize_histogram(b: &mut Bencher) {
         let image = gray_bench_image(500, 500);
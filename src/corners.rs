@@ -1,7 +1,17 @@
 //! Functions for detecting corners, also known as interest points.
 
-use crate::definitions::{Position, Score};
-use image::{GenericImageView, GrayImage};
+use crate::definitions::{Image, Position, Score};
+use crate::filter::gaussian_blur_f32;
+use crate::geometric_transformations::{pad_image, BorderMode};
+use crate::gradients::gradients_xy;
+use crate::rect::{Rect, Region};
+use crate::suppress::local_maxima;
+use image::imageops::{crop_imm, resize, FilterType};
+use image::{GenericImageView, GrayImage, Luma, Rgb};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use std::borrow::Cow;
+use std::cmp;
 
 /// A location and score for a detected corner.
 /// The scores need not be comparable between different
@@ -41,6 +51,61 @@ impl Score for Corner {
     }
 }
 
+/// A detected feature point with an orientation and scale,
+/// for use in feature description and matching.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Keypoint {
+    /// x-coordinate of the keypoint.
+    pub x: f32,
+    /// y-coordinate of the keypoint.
+    pub y: f32,
+    /// Score of the detected keypoint.
+    pub score: f32,
+    /// Orientation of the keypoint, in radians.
+    pub angle: f32,
+    /// Scale at which the keypoint was detected, relative to the input image.
+    pub scale: f32,
+}
+
+impl Keypoint {
+    /// A keypoint at location (x, y) with the given score, orientation and scale.
+    pub fn new(x: f32, y: f32, score: f32, angle: f32, scale: f32) -> Keypoint {
+        Keypoint {
+            x,
+            y,
+            score,
+            angle,
+            scale,
+        }
+    }
+}
+
+impl From<Corner> for Keypoint {
+    /// A keypoint at the location of `corner` with the same score,
+    /// an orientation of zero and a scale of one.
+    fn from(corner: Corner) -> Keypoint {
+        Keypoint::new(corner.x as f32, corner.y as f32, corner.score, 0.0, 1.0)
+    }
+}
+
+impl Position for Keypoint {
+    /// x-coordinate of the keypoint, rounded to the nearest pixel.
+    fn x(&self) -> u32 {
+        self.x.round() as u32
+    }
+
+    /// y-coordinate of the keypoint, rounded to the nearest pixel.
+    fn y(&self) -> u32 {
+        self.y.round() as u32
+    }
+}
+
+impl Score for Keypoint {
+    fn score(&self) -> f32 {
+        self.score
+    }
+}
+
 /// Variants of the [FAST](https://en.wikipedia.org/wiki/Features_from_accelerated_segment_test)
 /// corner detector. These classify a point based on its intensity relative to the 16 pixels
 /// in the Bresenham circle of radius 3 around it. A point P with intensity I is detected as a
@@ -49,6 +114,7 @@ impl Score for Corner {
 /// I - t, for some user-provided threshold t. The score of a corner is
 /// the greatest threshold for which the given pixel still qualifies as
 /// a corner.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
 pub enum Fast {
     /// Corners require a section of length as least nine.
     Nine,
@@ -75,8 +141,18 @@ pub fn corners_fast12(image: &GrayImage, threshold: u8) -> Vec<Corner> {
 
 /// Finds corners using FAST-9 features. See comment on Fast enum.
 pub fn corners_fast9(image: &GrayImage, threshold: u8) -> Vec<Corner> {
-    let (width, height) = image.dimensions();
     let mut corners = vec![];
+    corners_fast9_into(image, threshold, &mut corners);
+    corners
+}
+
+/// Finds corners using FAST-9 features, writing them into `corners`.
+///
+/// `corners` is cleared before any corners are added, but its allocation is
+/// reused. The corners found are the same as those returned by `corners_fast9`.
+pub fn corners_fast9_into(image: &GrayImage, threshold: u8, corners: &mut Vec<Corner>) {
+    let (width, height) = image.dimensions();
+    corners.clear();
 
     for y in 0..height {
         for x in 0..width {
@@ -86,6 +162,475 @@ pub fn corners_fast9(image: &GrayImage, threshold: u8) -> Vec<Corner> {
             }
         }
     }
+}
+
+/// Finds corners using FAST-9 features, returning an image of the same size as `image`
+/// in which corners have value 255 and all other pixels have value 0.
+///
+/// The corners are the same as those returned by `corners_fast9`, but corner scores
+/// are not computed.
+pub fn corners_fast9_mask(image: &GrayImage, threshold: u8) -> GrayImage {
+    let (width, height) = image.dimensions();
+    GrayImage::from_fn(width, height, |x, y| {
+        if is_corner_fast9(image, threshold, x, y) {
+            Luma([255])
+        } else {
+            Luma([0])
+        }
+    })
+}
+
+/// Finds FAST-9 corners in an image which is provided one row at a time, such as one
+/// read from a scanner or decoded incrementally.
+///
+/// Only the most recent seven rows are stored, as this is all that is needed to classify
+/// the pixels of the row three above the newest. The corners found, including their scores,
+/// are the same as those returned by [`corners_fast9`] for the full image.
+///
+/// # Examples
+/// ```
+/// use image::GrayImage;
+/// use imageproc::corners::{corners_fast9, FastDetector};
+///
+/// let image = GrayImage::from_fn(20, 20, |x, y| {
+///     image::Luma([if x > 8 && y > 8 { 255 } else { 0 }])
+/// });
+///
+/// let mut detector = FastDetector::new(image.width(), 10);
+/// let mut corners = vec![];
+/// for row in image.rows() {
+///     let row: Vec<u8> = row.map(|p| p[0]).collect();
+///     corners.extend(detector.push_row(&row));
+/// }
+///
+/// assert_eq!(corners, corners_fast9(&image, 10));
+/// ```
+///
+/// [`corners_fast9`]: fn.corners_fast9.html
+#[derive(Clone, Debug)]
+pub struct FastDetector {
+    threshold: u8,
+    /// The most recent rows, oldest first.
+    window: GrayImage,
+    rows_received: u32,
+}
+
+impl FastDetector {
+    /// Number of rows stored by the detector.
+    const WINDOW_HEIGHT: u32 = 7;
+
+    /// Creates a detector for images of the given width, using the given FAST threshold.
+    pub fn new(width: u32, threshold: u8) -> FastDetector {
+        FastDetector {
+            threshold,
+            window: GrayImage::new(width, Self::WINDOW_HEIGHT),
+            rows_received: 0,
+        }
+    }
+
+    /// Adds the next row of the image, returning the corners in the row three above it,
+    /// in order of increasing x. No corners are returned for the first six rows, as
+    /// pixels within three pixels of the image border can never be corners.
+    ///
+    /// # Panics
+    ///
+    /// If the length of `row` is not equal to the width passed to [`new`](#method.new).
+    pub fn push_row(&mut self, row: &[u8]) -> Vec<Corner> {
+        let width = self.window.width() as usize;
+        assert_eq!(row.len(), width, "row length must equal image width");
+
+        let filled = cmp::min(self.rows_received, Self::WINDOW_HEIGHT) as usize;
+        let buffer: &mut [u8] = &mut self.window;
+        if filled == Self::WINDOW_HEIGHT as usize {
+            buffer.copy_within(width.., 0);
+            buffer[(filled - 1) * width..].copy_from_slice(row);
+        } else {
+            buffer[filled * width..(filled + 1) * width].copy_from_slice(row);
+        }
+        self.rows_received += 1;
+
+        if self.rows_received < Self::WINDOW_HEIGHT {
+            return vec![];
+        }
+
+        let y = self.rows_received - 4;
+        (0..self.window.width())
+            .filter(|&x| is_corner_fast9(&self.window, self.threshold, x, 3))
+            .map(|x| {
+                let score = fast_corner_score(&self.window, self.threshold, x, 3, Fast::Nine);
+                Corner::new(x, y, score as f32)
+            })
+            .collect()
+    }
+}
+
+/// Options for FAST corner detection, combining the choice of detector with
+/// non-maximum suppression, a region of interest and handling of the image border.
+///
+/// # Examples
+/// ```
+/// use image::{GrayImage, Luma};
+/// use imageproc::corners::{corners_fast9, Fast, FastConfig};
+/// use imageproc::rect::Rect;
+///
+/// let image = GrayImage::from_fn(20, 20, |x, y| {
+///     Luma([if x > 8 && y > 8 { 255 } else { 0 }])
+/// });
+///
+/// // With no other options set this is equivalent to calling corners_fast9.
+/// let config = FastConfig::new(Fast::Nine, 10);
+/// assert_eq!(config.detect(&image), corners_fast9(&image, 10));
+///
+/// // Keep only the strongest corner within 5 pixels, in the bottom right of the image.
+/// let config = FastConfig::new(Fast::Nine, 10)
+///     .with_suppression(5)
+///     .with_roi(Rect::at(5, 5).of_size(15, 15));
+/// let corners = config.detect(&image);
+/// assert_eq!(corners.len(), 1);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct FastConfig {
+    /// Which FAST detector to use.
+    pub variant: Fast,
+    /// The FAST threshold. See the comment on [`Fast`](enum.Fast.html).
+    pub threshold: u8,
+    /// If set, only corners with the highest score in the square of side
+    /// `2 * radius + 1` centred on them are returned, as for
+    /// [`local_maxima`](../suppress/fn.local_maxima.html).
+    pub suppress_radius: Option<u32>,
+    /// If set, only corners within this rectangle are returned. Pixels outside the
+    /// rectangle are still used when classifying pixels near its edges.
+    pub roi: Option<Rect>,
+    /// If set, the image is padded by three pixels using this mode before detection, so
+    /// that pixels near the image border can be classified. Otherwise pixels within three
+    /// pixels of the border are never corners.
+    pub border: Option<BorderMode<Luma<u8>>>,
+}
+
+impl FastConfig {
+    /// Options for the given detector and threshold, with no suppression, no region of
+    /// interest, and no border handling.
+    pub fn new(variant: Fast, threshold: u8) -> FastConfig {
+        FastConfig {
+            variant,
+            threshold,
+            suppress_radius: None,
+            roi: None,
+            border: None,
+        }
+    }
+
+    /// Sets `suppress_radius` to `radius`.
+    pub fn with_suppression(mut self, radius: u32) -> FastConfig {
+        self.suppress_radius = Some(radius);
+        self
+    }
+
+    /// Sets `roi` to `rect`.
+    pub fn with_roi(mut self, rect: Rect) -> FastConfig {
+        self.roi = Some(rect);
+        self
+    }
+
+    /// Sets `border` to `mode`.
+    pub fn with_border(mut self, mode: BorderMode<Luma<u8>>) -> FastConfig {
+        self.border = Some(mode);
+        self
+    }
+
+    /// Finds the corners in `image` using these options, in row-major order.
+    pub fn detect(&self, image: &GrayImage) -> Vec<Corner> {
+        let (width, height) = image.dimensions();
+        let roi = match self.roi {
+            Some(roi) => roi.clamp_to(width, height),
+            None if width > 0 && height > 0 => Some(Rect::at(0, 0).of_size(width, height)),
+            None => None,
+        };
+        let roi = match roi {
+            Some(roi) => roi,
+            None => return vec![],
+        };
+
+        let (padded, offset) = match self.border {
+            Some(mode) => (Cow::Owned(pad_image(image, 3, 3, 3, 3, mode)), 3),
+            None => (Cow::Borrowed(image), 0),
+        };
+
+        // Crop to the region of interest plus the margin needed to classify its pixels.
+        let left = (roi.left() as u32 + offset).saturating_sub(3);
+        let top = (roi.top() as u32 + offset).saturating_sub(3);
+        let right = cmp::min(roi.right() as u32 + offset + 3, padded.width() - 1);
+        let bottom = cmp::min(roi.bottom() as u32 + offset + 3, padded.height() - 1);
+        let cropped = crop_imm(
+            padded.as_ref(),
+            left,
+            top,
+            right - left + 1,
+            bottom - top + 1,
+        )
+        .to_image();
+
+        let corners = match self.variant {
+            Fast::Nine => corners_fast9(&cropped, self.threshold),
+            Fast::Twelve => corners_fast12(&cropped, self.threshold),
+        };
+        let corners: Vec<Corner> = corners
+            .into_iter()
+            .map(|c| Corner::new(c.x + left - offset, c.y + top - offset, c.score))
+            .filter(|c| roi.contains(c.x as i32, c.y as i32))
+            .collect();
+
+        match self.suppress_radius {
+            Some(radius) => local_maxima(&corners, radius),
+            None => corners,
+        }
+    }
+}
+
+/// Returns an image of the same size as `image` in which each pixel is set to its
+/// [`fast_corner_score`] if it is a FAST corner for the given `threshold` and `variant`,
+/// and to 0 otherwise.
+///
+/// Corner scores are at least `threshold`, so if `threshold` is 0 then corners with score 0
+/// cannot be distinguished from other pixels. When the `rayon` feature is enabled rows are
+/// processed in parallel. The output does not depend on whether this feature is enabled.
+///
+/// [`fast_corner_score`]: fn.fast_corner_score.html
+pub fn fast_response_image(image: &GrayImage, threshold: u8, variant: Fast) -> GrayImage {
+    let (width, height) = image.dimensions();
+    let mut out = GrayImage::new(width, height);
+    if width == 0 {
+        return out;
+    }
+
+    let response = |x: u32, y: u32| {
+        let is_corner = match variant {
+            Fast::Nine => is_corner_fast9(image, threshold, x, y),
+            Fast::Twelve => is_corner_fast12(image, threshold, x, y),
+        };
+        if is_corner {
+            fast_corner_score(image, threshold, x, y, variant)
+        } else {
+            0
+        }
+    };
+
+    #[cfg(feature = "rayon")]
+    let rows = out.par_chunks_mut(width as usize);
+    #[cfg(not(feature = "rayon"))]
+    let rows = out.chunks_mut(width as usize);
+
+    rows.enumerate().for_each(|(y, row)| {
+        for (x, p) in row.iter_mut().enumerate() {
+            *p = response(x as u32, y as u32);
+        }
+    });
+
+    out
+}
+
+/// Finds corners using the [Harris corner detector], returning every pixel whose
+/// Harris response is greater than `threshold`.
+///
+/// The response at a pixel is `det(M) - k * trace(M)^2`, where `M` is the structure tensor
+/// of the image: the outer product of the image gradient with itself, smoothed by a Gaussian
+/// with standard deviation 1. Gradients are computed using the Sobel filter and scaled to
+/// intensity units per pixel, so responses have units of intensity<sup>4</sup> and thresholds
+/// are on a very different scale to those for FAST, which compare intensities directly.
+/// Edges have negative responses and flat regions have responses close to zero. Values
+/// of `k` between 0.04 and 0.06 are typical.
+///
+/// The score of each corner is its response. No non-maximum suppression is performed,
+/// so corners typically form small clusters; use
+/// [`local_maxima`](../suppress/fn.local_maxima.html) to reduce each to a single point.
+///
+/// [Harris corner detector]: https://en.wikipedia.org/wiki/Harris_corner_detector
+pub fn corners_harris(image: &GrayImage, k: f32, threshold: f32) -> Vec<Corner> {
+    corners_from_structure_tensor(image, threshold, |a, b, c| {
+        a * c - b * b - k * (a + c) * (a + c)
+    })
+}
+
+/// Finds corners using the Shi–Tomasi detector, returning every pixel for which the
+/// smaller eigenvalue of the structure tensor is greater than `threshold`.
+///
+/// The structure tensor is computed as for [`corners_harris`], so responses have units of
+/// intensity<sup>2</sup>. Edges and flat regions have responses close to zero. As for
+/// `corners_harris`, the score of each corner is its response and no non-maximum
+/// suppression is performed.
+///
+/// [`corners_harris`]: fn.corners_harris.html
+pub fn corners_shi_tomasi(image: &GrayImage, threshold: f32) -> Vec<Corner> {
+    corners_from_structure_tensor(image, threshold, |a, b, c| {
+        (a + c) / 2.0 - (((a - c) / 2.0).powi(2) + b * b).sqrt()
+    })
+}
+
+/// Returns the pixels for which `response` applied to the entries `(a, b, c)` of the
+/// structure tensor `[[a, b], [b, c]]` is greater than `threshold`.
+fn corners_from_structure_tensor<F>(image: &GrayImage, threshold: f32, response: F) -> Vec<Corner>
+where
+    F: Fn(f32, f32, f32) -> f32,
+{
+    if image.width() == 0 || image.height() == 0 {
+        return vec![];
+    }
+
+    // The Sobel filters have a gain of 8.
+    let (gx, gy) = gradients_xy(image);
+    let tensor: Image<Rgb<f32>> = Image::from_fn(image.width(), image.height(), |x, y| {
+        let dx = gx.get_pixel(x, y)[0] as f32 / 8.0;
+        let dy = gy.get_pixel(x, y)[0] as f32 / 8.0;
+        Rgb([dx * dx, dx * dy, dy * dy])
+    });
+    let tensor = gaussian_blur_f32(&tensor, 1.0);
+
+    tensor
+        .enumerate_pixels()
+        .filter_map(|(x, y, m)| {
+            let r = response(m[0], m[1], m[2]);
+            if r > threshold {
+                Some(Corner::new(x, y, r))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// A corner detector, together with a threshold of the type appropriate to it.
+///
+/// Thresholds for FAST compare pixel intensities directly, whereas those for
+/// the Harris and Shi–Tomasi detectors compare responses computed from image gradients,
+/// which are on a much larger scale. See the documentation of each detector for details.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum CornerDetector {
+    /// The FAST detector. See [`FastConfig`](struct.FastConfig.html) for more options.
+    Fast {
+        /// Which FAST detector to use.
+        variant: Fast,
+        /// The FAST threshold.
+        threshold: u8,
+    },
+    /// The Harris detector. See [`corners_harris`](fn.corners_harris.html).
+    Harris {
+        /// The sensitivity parameter.
+        k: f32,
+        /// The minimum Harris response.
+        threshold: f32,
+    },
+    /// The Shi–Tomasi detector. See [`corners_shi_tomasi`](fn.corners_shi_tomasi.html).
+    ShiTomasi {
+        /// The minimum value of the smaller eigenvalue of the structure tensor.
+        threshold: f32,
+    },
+}
+
+impl CornerDetector {
+    /// Finds the corners in `image` using this detector, in row-major order.
+    ///
+    /// The `score` of each corner is on the scale of this detector's threshold,
+    /// so scores from different detectors are not comparable.
+    pub fn detect(&self, image: &GrayImage) -> Vec<Corner> {
+        match *self {
+            CornerDetector::Fast { variant, threshold } => {
+                FastConfig::new(variant, threshold).detect(image)
+            }
+            CornerDetector::Harris { k, threshold } => corners_harris(image, k, threshold),
+            CornerDetector::ShiTomasi { threshold } => corners_shi_tomasi(image, threshold),
+        }
+    }
+}
+
+/// A corner detected at some level of an image pyramid.
+///
+/// The `Position` of a `ScaledCorner` is its location rounded to the nearest pixel of the
+/// full resolution image, so corners from all levels can be passed together to
+/// [`local_maxima`](../suppress/fn.local_maxima.html).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ScaledCorner {
+    /// x-coordinate of the corner, mapped back to the full resolution image.
+    pub x: f32,
+    /// y-coordinate of the corner, mapped back to the full resolution image.
+    pub y: f32,
+    /// Pyramid level at which the corner was detected. Level 0 is the input image.
+    pub level: usize,
+    /// Score of the detected corner.
+    pub score: f32,
+}
+
+impl Position for ScaledCorner {
+    fn x(&self) -> u32 {
+        self.x.round() as u32
+    }
+    fn y(&self) -> u32 {
+        self.y.round() as u32
+    }
+}
+
+impl Score for ScaledCorner {
+    fn score(&self) -> f32 {
+        self.score
+    }
+}
+
+/// Finds FAST-9 corners at each level of an image pyramid.
+///
+/// Level 0 is the input image, and each subsequent level is downsampled
+/// from the previous level by a factor of `scale`. Corner locations are mapped
+/// back to the coordinates of the input image, and the corners from each level
+/// are returned in level order. The pyramid stops early if a level would be empty.
+///
+/// Locations are mapped between levels treating each pixel as a unit square with its
+/// centre at integer coordinates, so pixel `x` of a level whose width is `1 / r` times
+/// that of the input maps to `(x + 0.5) * r - 0.5`.
+///
+/// # Panics
+///
+/// If `scale` is not greater than 1.
+pub fn corners_fast9_pyramid(
+    image: &GrayImage,
+    threshold: u8,
+    levels: usize,
+    scale: f32,
+) -> Vec<ScaledCorner> {
+    assert!(scale > 1.0, "scale must be greater than 1");
+
+    let (width, height) = image.dimensions();
+    let mut corners = vec![];
+    let mut downsampled: Option<GrayImage> = None;
+
+    for level in 0..levels {
+        if level > 0 {
+            let previous = downsampled.as_ref().unwrap_or(image);
+            let level_width = (previous.width() as f32 / scale).round() as u32;
+            let level_height = (previous.height() as f32 / scale).round() as u32;
+            if level_width == 0 || level_height == 0 {
+                break;
+            }
+            downsampled = Some(resize(
+                previous,
+                level_width,
+                level_height,
+                FilterType::Triangle,
+            ));
+        }
+        let current = downsampled.as_ref().unwrap_or(image);
+
+        let x_ratio = width as f32 / current.width() as f32;
+        let y_ratio = height as f32 / current.height() as f32;
+
+        corners.extend(
+            corners_fast9(current, threshold)
+                .into_iter()
+                .map(|c| ScaledCorner {
+                    x: (c.x as f32 + 0.5) * x_ratio - 0.5,
+                    y: (c.y as f32 + 0.5) * y_ratio - 0.5,
+                    level,
+                    score: c.score,
+                }),
+        );
+    }
 
     corners
 }
@@ -97,7 +642,14 @@ pub fn corners_fast9(image: &GrayImage, threshold: u8) -> Vec<Corner> {
 /// Note that the corner check uses a strict inequality, so if
 /// the smallest intensity difference between the center pixel
 /// and a corner pixel is n then the corner will have a score of n - 1.
+///
+/// Pixels within 3 pixels of the image border can never be corners,
+/// so for these `threshold` is returned immediately.
 pub fn fast_corner_score(image: &GrayImage, threshold: u8, x: u32, y: u32, variant: Fast) -> u8 {
+    if !is_fast_interior(image, x, y) {
+        return threshold;
+    }
+
     let mut max = 255u8;
     let mut min = threshold;
 
@@ -122,6 +674,75 @@ pub fn fast_corner_score(image: &GrayImage, threshold: u8, x: u32, y: u32, varia
     }
 }
 
+/// Returns the length of the longest contiguous section of the FAST circle around `(x, y)`
+/// whose pixels are all brighter than the centre pixel by more than `threshold`, or all darker
+/// than it by more than `threshold`. See the comment on [`Fast`] for details.
+///
+/// Returns `None` if this length is less than 9, i.e. exactly when `(x, y)` is not a FAST-9
+/// corner for this `threshold`. Pixels within 3 pixels of the image border can never be
+/// corners, so `None` is returned for these.
+///
+/// [`Fast`]: enum.Fast.html
+pub fn fast_arc_length(image: &GrayImage, threshold: u8, x: u32, y: u32) -> Option<u8> {
+    if !is_fast_interior(image, x, y) {
+        return None;
+    }
+
+    // UNSAFETY JUSTIFICATION
+    //  The check above ensures that the precondition of get_circle holds.
+    let (c, circle) = unsafe {
+        let p0 = image.unsafe_get_pixel(x, y - 3)[0] as i16;
+        let p4 = image.unsafe_get_pixel(x + 3, y)[0] as i16;
+        let p8 = image.unsafe_get_pixel(x, y + 3)[0] as i16;
+        let p12 = image.unsafe_get_pixel(x - 3, y)[0] as i16;
+        (
+            image.unsafe_get_pixel(x, y)[0] as i16,
+            get_circle(image, x, y, p0, p4, p8, p12),
+        )
+    };
+    let low_thresh = c - threshold as i16;
+    let high_thresh = c + threshold as i16;
+
+    let longest = cmp::max(
+        longest_span(&circle, |p| *p > high_thresh),
+        longest_span(&circle, |p| *p < low_thresh),
+    );
+    if longest >= 9 {
+        Some(longest as u8)
+    } else {
+        None
+    }
+}
+
+/// Length of the longest contiguous section of the ring `circle` all of whose
+/// elements satisfy `f`.
+fn longest_span<F: Fn(&i16) -> bool>(circle: &[i16; 16], f: F) -> usize {
+    // Start scanning just after a non-matching element, so that no section wraps around.
+    let start = match circle.iter().position(|c| !f(c)) {
+        Some(i) => i + 1,
+        None => return circle.len(),
+    };
+
+    let mut longest = 0;
+    let mut current = 0;
+    for i in 0..circle.len() {
+        if f(&circle[(start + i) % circle.len()]) {
+            current += 1;
+            longest = cmp::max(longest, current);
+        } else {
+            current = 0;
+        }
+    }
+    longest
+}
+
+/// True if the FAST circle around (x, y) lies entirely within the image.
+#[inline]
+fn is_fast_interior(image: &GrayImage, x: u32, y: u32) -> bool {
+    let (width, height) = image.dimensions();
+    x < u32::MAX - 3 && y < u32::MAX - 3 && x >= 3 && y >= 3 && x + 3 < width && y + 3 < height
+}
+
 // Note [FAST circle labels]
 //
 //          15 00 01
@@ -146,14 +767,7 @@ fn is_corner_fast9(image: &GrayImage, threshold: u8, x: u32, y: u32) -> bool {
     //      access pixels with x-coordinate in the range [x - 3, x + 3] and
     //      y-coordinate in the range [y - 3, y + 3]. The precondition below
     //      guarantees that these are within image bounds.
-    let (width, height) = image.dimensions();
-    if x >= u32::max_value() - 3
-        || y >= u32::max_value() - 3
-        || x < 3
-        || y < 3
-        || width <= x + 3
-        || height <= y + 3
-    {
+    if !is_fast_interior(image, x, y) {
         return false;
     }
 
@@ -162,13 +776,14 @@ fn is_corner_fast9(image: &GrayImage, threshold: u8, x: u32, y: u32) -> bool {
     let low_thresh: i16 = c as i16 - threshold as i16;
     let high_thresh: i16 = c as i16 + threshold as i16;
 
-    // See Note [FAST circle labels]
+    // See Note [FAST circle labels]. The top, right, bottom and left pixels have
+    // labels 0, 4, 8 and 12, and must be passed to get_circle in this order.
     // JUSTIFICATION - see comment at the start of this function
     let (p0, p4, p8, p12) = unsafe {
         (
             image.unsafe_get_pixel(x, y - 3)[0] as i16,
-            image.unsafe_get_pixel(x, y + 3)[0] as i16,
             image.unsafe_get_pixel(x + 3, y)[0] as i16,
+            image.unsafe_get_pixel(x, y + 3)[0] as i16,
             image.unsafe_get_pixel(x - 3, y)[0] as i16,
         )
     };
@@ -207,14 +822,7 @@ fn is_corner_fast12(image: &GrayImage, threshold: u8, x: u32, y: u32) -> bool {
     //      access pixels with x-coordinate in the range [x - 3, x + 3] and
     //      y-coordinate in the range [y - 3, y + 3]. The precondition below
     //      guarantees that these are within image bounds.
-    let (width, height) = image.dimensions();
-    if x >= u32::max_value() - 3
-        || y >= u32::max_value() - 3
-        || x < 3
-        || y < 3
-        || width <= x + 3
-        || height <= y + 3
-    {
+    if !is_fast_interior(image, x, y) {
         return false;
     }
 
@@ -313,26 +921,47 @@ unsafe fn get_circle(
 /// True if the circle has a contiguous section of at least the given length, all
 /// of whose pixels have intensities strictly greater than the threshold.
 fn has_bright_span(circle: &[i16; 16], length: u8, threshold: i16) -> bool {
-    search_span(circle, length, |c| *c > threshold)
+    has_contiguous_span(circle, length as usize, |c| *c > threshold)
 }
 
 /// True if the circle has a contiguous section of at least the given length, all
 /// of whose pixels have intensities strictly less than the threshold.
 fn has_dark_span(circle: &[i16; 16], length: u8, threshold: i16) -> bool {
-    search_span(circle, length, |c| *c < threshold)
+    has_contiguous_span(circle, length as usize, |c| *c < threshold)
 }
 
-/// True if the circle has a contiguous section of at least the given length, all
-/// of whose pixels match f condition.
-fn search_span<F>(circle: &[i16; 16], length: u8, f: F) -> bool
+/// True if `circle` has a contiguous section of at least `length` elements, all of which
+/// satisfy `f`.
+///
+/// `circle` is treated as a ring, so sections may wrap around from its last element to its
+/// first. This is the test used by the FAST detectors on the 16 pixels around a candidate
+/// corner, and can be used to build other detectors based on rings of pixels.
+///
+/// Returns `true` if `length` is 0, and `false` if `length` is greater than `circle.len()`.
+///
+/// # Examples
+/// ```
+/// use imageproc::corners::has_contiguous_span;
+///
+/// let ring = [9, 9, 0, 0, 0, 9, 9, 9];
+/// assert!(has_contiguous_span(&ring, 5, |&c| c > 5));
+/// assert!(!has_contiguous_span(&ring, 6, |&c| c > 5));
+/// ```
+pub fn has_contiguous_span<T, F>(circle: &[T], length: usize, f: F) -> bool
 where
-    F: Fn(&i16) -> bool,
+    F: Fn(&T) -> bool,
 {
-    if length > 16 {
+    if length == 0 {
+        return true;
+    }
+    if length > circle.len() {
         return false;
     }
 
-    let mut nb_ok = 0u8;
+    // Length of the run of matching elements at the start of circle, which
+    // is recorded at the first non-matching element so that it can be joined
+    // to any run at the end of circle.
+    let mut nb_ok = 0;
     let mut nb_ok_start = None;
 
     for c in circle.iter() {
@@ -349,14 +978,239 @@ where
         }
     }
 
-    nb_ok + nb_ok_start.unwrap() >= length
+    // If every element matched then we returned above, as length <= circle.len().
+    match nb_ok_start {
+        Some(start) => nb_ok + start >= length,
+        None => false,
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use image::ImageBuffer;
     use test::{black_box, Bencher};
 
+    #[test]
+    fn test_has_contiguous_span_all_true() {
+        let circle = [1i16; 16];
+        for &length in &[0, 1, 9, 12, 16] {
+            assert!(has_contiguous_span(&circle, length, |&c| c > 0));
+        }
+        assert!(!has_contiguous_span(&circle, 17, |&c| c > 0));
+    }
+
+    #[test]
+    fn test_has_contiguous_span_all_false() {
+        let circle = [0i16; 16];
+        assert!(has_contiguous_span(&circle, 0, |&c| c > 0));
+        for &length in &[1, 9, 12, 16, 17] {
+            assert!(!has_contiguous_span(&circle, length, |&c| c > 0));
+        }
+    }
+
+    #[test]
+    fn test_has_contiguous_span_wraps_around() {
+        let mut circle = [1i16; 16];
+        circle[4] = 0;
+        circle[5] = 0;
+        // The longest span runs from index 6 through the end and back to index 3.
+        assert!(has_contiguous_span(&circle, 14, |&c| c > 0));
+        assert!(!has_contiguous_span(&circle, 15, |&c| c > 0));
+
+        circle[0] = 0;
+        assert!(has_contiguous_span(&circle, 10, |&c| c > 0));
+        assert!(!has_contiguous_span(&circle, 11, |&c| c > 0));
+    }
+
+    #[test]
+    fn test_has_contiguous_span_empty() {
+        let circle: [i16; 0] = [];
+        assert!(has_contiguous_span(&circle, 0, |&c| c > 0));
+        assert!(!has_contiguous_span(&circle, 1, |&c| c > 0));
+    }
+
+    #[test]
+    fn test_fast_detector_matches_corners_fast9() {
+        let image = GrayImage::from_fn(30, 25, |x, y| Luma([((x * 31 + y * 17) ^ (x * y)) as u8]));
+
+        let mut detector = FastDetector::new(image.width(), 20);
+        let mut corners = vec![];
+        for y in 0..image.height() {
+            let row: Vec<u8> = (0..image.width())
+                .map(|x| image.get_pixel(x, y)[0])
+                .collect();
+            corners.extend(detector.push_row(&row));
+        }
+
+        let expected = corners_fast9(&image, 20);
+        assert!(!expected.is_empty());
+        assert_eq!(corners, expected);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_fast_detector_rejects_row_of_wrong_length() {
+        let mut detector = FastDetector::new(10, 20);
+        detector.push_row(&[0; 9]);
+    }
+
+    #[test]
+    fn test_fast_config_matches_free_functions() {
+        let image = GrayImage::from_fn(30, 25, |x, y| Luma([((x * 31 + y * 17) ^ (x * y)) as u8]));
+        let corners = corners_fast12(&image, 20);
+        assert!(!corners.is_empty());
+
+        let config = FastConfig::new(Fast::Twelve, 20);
+        assert_eq!(config.detect(&image), corners);
+        assert_eq!(
+            config.with_suppression(4).detect(&image),
+            local_maxima(&corners, 4)
+        );
+
+        let roi = Rect::at(5, 6).of_size(10, 8);
+        let in_roi: Vec<Corner> = corners
+            .iter()
+            .copied()
+            .filter(|c| roi.contains(c.x as i32, c.y as i32))
+            .collect();
+        assert!(!in_roi.is_empty());
+        assert_eq!(config.with_roi(roi).detect(&image), in_roi);
+    }
+
+    #[test]
+    fn test_fast_config_border() {
+        // A bright square in the top left corner is only detected if the border is handled.
+        let image = GrayImage::from_fn(12, 12, |x, y| Luma([if x < 3 && y < 3 { 255 } else { 0 }]));
+        let config = FastConfig::new(Fast::Nine, 20);
+        assert!(config.detect(&image).is_empty());
+
+        let corners = config
+            .with_border(BorderMode::Constant(Luma([0])))
+            .detect(&image);
+        assert!(corners.contains(&Corner::new(2, 2, 254.0)));
+
+        let outside = config.with_roi(Rect::at(20, 20).of_size(5, 5));
+        assert!(outside.detect(&image).is_empty());
+
+        let reflect = config.with_border(BorderMode::Reflect);
+        assert!(reflect.detect(&GrayImage::new(0, 0)).is_empty());
+    }
+
+    #[test]
+    fn test_gradient_corner_detectors_find_square_corners() {
+        let image = GrayImage::from_fn(30, 30, |x, y| {
+            Luma([if (10..20).contains(&x) && (10..20).contains(&y) {
+                200
+            } else {
+                0
+            }])
+        });
+
+        for detector in &[
+            CornerDetector::Harris {
+                k: 0.05,
+                threshold: 1.0,
+            },
+            CornerDetector::ShiTomasi { threshold: 1.0 },
+        ] {
+            // Responses form a cluster around each corner of the square, peaking at the corner.
+            let peaks: Vec<(u32, u32)> = local_maxima(&detector.detect(&image), 4)
+                .iter()
+                .map(|c| (c.x, c.y))
+                .collect();
+            assert_eq!(peaks, vec![(10, 10), (19, 10), (10, 19), (19, 19)]);
+        }
+
+        assert_eq!(
+            CornerDetector::Fast {
+                variant: Fast::Nine,
+                threshold: 20
+            }
+            .detect(&image),
+            corners_fast9(&image, 20)
+        );
+        assert!(corners_harris(&GrayImage::new(0, 0), 0.05, 1.0).is_empty());
+    }
+
+    #[test]
+    fn test_get_circle_matches_bresenham_circle_points() {
+        use crate::geometry::bresenham_circle_points;
+
+        let offsets = bresenham_circle_points(3);
+        let mut image = GrayImage::new(7, 7);
+        for (i, &(dx, dy)) in offsets.iter().enumerate() {
+            image.put_pixel((3 + dx) as u32, (3 + dy) as u32, Luma([i as u8 + 1]));
+        }
+
+        let circle = unsafe { get_circle(&image, 3, 3, 1, 5, 9, 13) };
+        let expected: Vec<i16> = (1..17).collect();
+        assert_eq!(circle.to_vec(), expected);
+    }
+
+    #[test]
+    fn test_corners_fast9_mask_matches_corners_fast9() {
+        let image = gray_image!(
+            10, 10, 10, 10, 10, 10, 10, 10, 10;
+            10, 10, 10, 10, 10, 10, 10, 10, 10;
+            10, 10, 10, 10, 10, 10, 10, 10, 10;
+            10, 10, 10, 10, 10, 10, 10, 10, 10;
+            10, 10, 10, 10, 90, 90, 90, 90, 90;
+            10, 10, 10, 10, 90, 90, 90, 90, 90;
+            10, 10, 10, 10, 90, 90, 90, 90, 90;
+            10, 10, 10, 10, 90, 90, 90, 90, 90;
+            10, 10, 10, 10, 90, 90, 90, 90, 90);
+
+        let corners = corners_fast9(&image, 20);
+        assert!(!corners.is_empty());
+
+        let mut expected = GrayImage::new(9, 9);
+        for c in corners {
+            expected.put_pixel(c.x, c.y, Luma([255]));
+        }
+        assert_pixels_eq!(corners_fast9_mask(&image, 20), expected);
+    }
+
+    #[test]
+    fn test_fast_response_image_matches_corner_scores() {
+        let image = ImageBuffer::from_fn(24, 17, |x, y| {
+            Luma([((x * 37 + y * 91 + x * y * 13) % 256) as u8])
+        });
+
+        for &variant in &[Fast::Nine, Fast::Twelve] {
+            let response = fast_response_image(&image, 30, variant);
+            let mut expected = GrayImage::new(24, 17);
+            for y in 0..17 {
+                for x in 0..24 {
+                    let is_corner = match variant {
+                        Fast::Nine => is_corner_fast9(&image, 30, x, y),
+                        Fast::Twelve => is_corner_fast12(&image, 30, x, y),
+                    };
+                    if is_corner {
+                        let score = fast_corner_score(&image, 30, x, y, variant);
+                        expected.put_pixel(x, y, Luma([score]));
+                    }
+                }
+            }
+            assert!(expected.pixels().any(|p| p[0] > 0));
+            assert_pixels_eq!(response, expected);
+        }
+
+        assert_eq!(
+            fast_response_image(&GrayImage::new(0, 4), 10, Fast::Nine).dimensions(),
+            (0, 4)
+        );
+    }
+
+    #[test]
+    fn test_keypoint_from_corner() {
+        let keypoint = Keypoint::from(Corner::new(4, 7, 12.0));
+        assert_eq!(keypoint, Keypoint::new(4.0, 7.0, 12.0, 0.0, 1.0));
+        assert_eq!((keypoint.x(), keypoint.y()), (4, 7));
+        assert_eq!(keypoint.score(), 12.0);
+        assert_eq!(Keypoint::new(2.6, 3.4, 1.0, 0.0, 1.0).x(), 3);
+    }
+
     #[test]
     fn test_is_corner_fast12_12_contiguous_darker_pixels() {
         let image = gray_image!(
@@ -459,6 +1313,82 @@ mod tests {
         assert_eq!(score, 9);
     }
 
+    #[test]
+    fn test_corners_fast9_pyramid() {
+        let mut image = GrayImage::new(40, 40);
+        for y in 10..30 {
+            for x in 10..30 {
+                image.put_pixel(x, y, image::Luma([200]));
+            }
+        }
+
+        let pyramid = corners_fast9_pyramid(&image, 20, 3, 2.0);
+
+        let level0: Vec<ScaledCorner> = corners_fast9(&image, 20)
+            .into_iter()
+            .map(|c| ScaledCorner {
+                x: c.x as f32,
+                y: c.y as f32,
+                level: 0,
+                score: c.score,
+            })
+            .collect();
+        assert!(!level0.is_empty());
+        assert_eq!(pyramid[..level0.len()], level0[..]);
+
+        let levels: Vec<usize> = pyramid.iter().map(|c| c.level).collect();
+        assert!(levels.windows(2).all(|w| w[0] <= w[1]));
+        assert!(levels.contains(&1));
+        assert!(pyramid
+            .iter()
+            .all(|c| c.x >= 0.0 && c.x < 40.0 && c.y >= 0.0 && c.y < 40.0));
+
+        // Pixel k at level 1 covers input pixels 2k and 2k + 1, so maps to 2k + 0.5.
+        for c in pyramid.iter().filter(|c| c.level == 1) {
+            assert_eq!((c.x - 0.5) % 2.0, 0.0);
+            assert_eq!((c.y - 0.5) % 2.0, 0.0);
+        }
+
+        // Corners from all levels can be suppressed together.
+        let suppressed = local_maxima(&pyramid, 3);
+        assert!(!suppressed.is_empty() && suppressed.len() < pyramid.len());
+
+        assert!(corners_fast9_pyramid(&image, 20, 0, 2.0).is_empty());
+    }
+
+    #[test]
+    fn test_fast_corner_score_near_image_boundary() {
+        let image = gray_image!(
+            10, 10, 00, 00, 00, 10, 10;
+            10, 00, 10, 10, 10, 00, 10;
+            00, 10, 10, 10, 10, 10, 10;
+            00, 10, 10, 10, 10, 10, 10;
+            00, 10, 10, 10, 10, 10, 10;
+            10, 00, 10, 10, 10, 10, 10;
+            10, 10, 00, 00, 00, 10, 10);
+
+        assert_eq!(fast_corner_score(&image, 5, 1, 1, Fast::Twelve), 5);
+        assert_eq!(fast_corner_score(&image, 5, 4, 3, Fast::Nine), 5);
+        assert_eq!(fast_corner_score(&image, 5, 3, 6, Fast::Nine), 5);
+    }
+
+    #[test]
+    fn test_corners_fast9_into_clears_output() {
+        let image = gray_image!(
+            10, 10, 00, 00, 00, 10, 10;
+            10, 00, 10, 10, 10, 00, 10;
+            00, 10, 10, 10, 10, 10, 10;
+            00, 10, 10, 10, 10, 10, 10;
+            00, 10, 10, 10, 10, 10, 10;
+            10, 00, 10, 10, 10, 10, 10;
+            10, 10, 10, 10, 10, 10, 10);
+
+        let mut corners = vec![Corner::new(0, 0, 1.0)];
+        corners_fast9_into(&image, 8, &mut corners);
+        assert_eq!(corners, corners_fast9(&image, 8));
+        assert_eq!(corners, vec![Corner::new(3, 3, 9.0)]);
+    }
+
     #[test]
     fn test_is_corner_fast9_9_contiguous_darker_pixels() {
         let image = gray_image!(
@@ -473,6 +1403,63 @@ mod tests {
         assert_eq!(is_corner_fast9(&image, 8, 3, 3), true);
     }
 
+    #[test]
+    fn test_fast_arc_length() {
+        let image = gray_image!(
+            10, 10, 00, 00, 00, 10, 10;
+            10, 00, 10, 10, 10, 00, 10;
+            00, 10, 10, 10, 10, 10, 10;
+            00, 10, 10, 10, 10, 10, 10;
+            00, 10, 10, 10, 10, 10, 10;
+            10, 00, 10, 10, 10, 10, 10;
+            10, 10, 10, 10, 10, 10, 10);
+
+        // The dark section wraps around from label 10 to label 2.
+        assert_eq!(fast_arc_length(&image, 8, 3, 3), Some(9));
+        assert_eq!(fast_arc_length(&image, 10, 3, 3), None);
+        assert_eq!(fast_arc_length(&image, 8, 2, 3), None);
+
+        let uniform = GrayImage::from_pixel(7, 7, Luma([20]));
+        let mut ring = uniform.clone();
+        ring.put_pixel(3, 3, Luma([0]));
+        assert_eq!(fast_arc_length(&ring, 5, 3, 3), Some(16));
+        assert_eq!(fast_arc_length(&uniform, 5, 3, 3), None);
+    }
+
+    #[test]
+    fn test_is_corner_fast9_arc_through_right_pixel() {
+        // The bright section runs from label 12 to label 4, so contains the right pixel
+        // (label 4) but not the bottom pixel (label 8). Passing these two pixels to
+        // get_circle in each other's positions breaks the section in two.
+        let image = gray_image!(
+            10, 10, 50, 50, 50, 10, 10;
+            10, 50, 10, 10, 10, 50, 10;
+            50, 10, 10, 10, 10, 10, 50;
+            50, 10, 10, 10, 10, 10, 50;
+            10, 10, 10, 10, 10, 10, 10;
+            10, 10, 10, 10, 10, 10, 10;
+            10, 10, 10, 10, 10, 10, 10);
+
+        assert!(is_corner_fast9(&image, 8, 3, 3));
+        let corners = corners_fast9(&image, 8);
+        assert!(corners.iter().any(|c| (c.x, c.y) == (3, 3)));
+    }
+
+    #[test]
+    fn test_fast_arc_length_matches_is_corner_fast9() {
+        let image = ImageBuffer::from_fn(20, 20, |x, y| {
+            Luma([((x * 37 + y * 91 + x * y * 13) % 256) as u8])
+        });
+        for y in 0..20 {
+            for x in 0..20 {
+                assert_eq!(
+                    fast_arc_length(&image, 30, x, y).is_some(),
+                    is_corner_fast9(&image, 30, x, y)
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_is_corner_fast9_9_contiguous_lighter_pixels() {
         let image = gray_image!(
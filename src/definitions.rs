@@ -1,6 +1,7 @@
 //! Trait definitions and type aliases.
 
 use image::{Bgr, Bgra, ImageBuffer, Luma, LumaA, Pixel, Rgb, Rgba};
+use std::cmp::Ordering;
 use std::{i16, u16, u8};
 
 /// An `ImageBuffer` containing Pixels of type P with storage `Vec<P::Subpixel>`.
@@ -13,6 +14,11 @@ use std::{i16, u16, u8};
 pub type Image<P> = ImageBuffer<P, Vec<<P as Pixel>::Subpixel>>;
 
 /// Pixels which have a named Black value.
+///
+/// For integer subpixel types black has all color channels equal to zero, and
+/// white has all color channels equal to the subpixel maximum. For floating point
+/// subpixel types white has all color channels equal to `1.0`. Alpha channels are
+/// always fully opaque.
 pub trait HasBlack {
     /// Returns a black pixel of this type.
     fn black() -> Self;
@@ -77,12 +83,37 @@ impl_black_white!(
     Bgra([u16::MAX, u16::MAX, u16::MAX, u16::MAX])
 );
 
+impl_black_white!(Luma<f32>, Luma([0.0]), Luma([1.0]));
+impl_black_white!(LumaA<f32>, LumaA([0.0, 1.0]), LumaA([1.0, 1.0]));
+impl_black_white!(Rgb<f32>, Rgb([0.0; 3]), Rgb([1.0; 3]));
+impl_black_white!(Rgba<f32>, Rgba([0.0, 0.0, 0.0, 1.0]), Rgba([1.0; 4]));
+impl_black_white!(Bgr<f32>, Bgr([0.0; 3]), Bgr([1.0; 3]));
+impl_black_white!(Bgra<f32>, Bgra([0.0, 0.0, 0.0, 1.0]), Bgra([1.0; 4]));
+
 /// Something with a 2d position.
 pub trait Position {
     /// x-coordinate.
     fn x(&self) -> u32;
     /// y-coordinate.
     fn y(&self) -> u32;
+
+    /// Euclidean distance in pixels between this position and `other`.
+    fn pixel_distance<P: Position>(&self, other: &P) -> f64
+    where
+        Self: Sized,
+    {
+        let dx = self.x() as f64 - other.x() as f64;
+        let dy = self.y() as f64 - other.y() as f64;
+        (dx * dx + dy * dy).sqrt()
+    }
+
+    /// True if `other` is no more than `radius` pixels from this position.
+    fn within<P: Position>(&self, other: &P, radius: f64) -> bool
+    where
+        Self: Sized,
+    {
+        self.pixel_distance(other) <= radius
+    }
 }
 
 /// Something with a score.
@@ -91,8 +122,56 @@ pub trait Score {
     fn score(&self) -> f32;
 }
 
+/// Helpers for working with slices of scored items, such as the
+/// corners returned by a detector.
+///
+/// `NaN` scores are treated as lower than all other scores.
+pub trait ScoreExt<T> {
+    /// Returns references to all items, ordered by decreasing score.
+    /// Items with equal scores keep their original relative order.
+    fn sorted_by_score_desc(&self) -> Vec<&T>;
+
+    /// Returns the item with the highest score, or `None` if there are no items.
+    /// If several items share the highest score then the first is returned.
+    fn max_by_score(&self) -> Option<&T>;
+}
+
+impl<T: Score> ScoreExt<T> for [T] {
+    fn sorted_by_score_desc(&self) -> Vec<&T> {
+        let mut sorted: Vec<&T> = self.iter().collect();
+        sorted.sort_by(|a, b| compare_scores(b.score(), a.score()));
+        sorted
+    }
+
+    fn max_by_score(&self) -> Option<&T> {
+        let mut iter = self.iter();
+        let first = iter.next()?;
+        Some(iter.fold(first, |best, t| {
+            if compare_scores(t.score(), best.score()) == Ordering::Greater {
+                t
+            } else {
+                best
+            }
+        }))
+    }
+}
+
+/// Total order on scores in which `NaN` is less than all other values.
+fn compare_scores(a: f32, b: f32) -> Ordering {
+    match (a.is_nan(), b.is_nan()) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Less,
+        (false, true) => Ordering::Greater,
+        (false, false) => a.partial_cmp(&b).unwrap(),
+    }
+}
+
 /// A type to which we can clamp a value of type T.
 /// Implementations are not required to handle `NaN`s gracefully.
+///
+/// Values below the minimum of the target type saturate to that minimum,
+/// and values above its maximum saturate to that maximum. For example,
+/// clamping the `i32` values `-5` and `300` to `u8` gives `0` and `255`.
 pub trait Clamp<T> {
     /// Clamp `x` to a valid value for this type.
     fn clamp(x: T) -> Self;
@@ -169,11 +248,133 @@ implement_clamp!(
     i16::MAX as i32
 );
 
+implement_clamp!(i64, u8, u8::MIN, u8::MAX, u8::MIN as i64, u8::MAX as i64);
+implement_clamp!(
+    i64,
+    u16,
+    u16::MIN,
+    u16::MAX,
+    u16::MIN as i64,
+    u16::MAX as i64
+);
+implement_clamp!(
+    i64,
+    i16,
+    i16::MIN,
+    i16::MAX,
+    i16::MIN as i64,
+    i16::MAX as i64
+);
+implement_clamp!(
+    i64,
+    u32,
+    u32::MIN,
+    u32::MAX,
+    u32::MIN as i64,
+    u32::MAX as i64
+);
+implement_clamp!(
+    i64,
+    i32,
+    i32::MIN,
+    i32::MAX,
+    i32::MIN as i64,
+    i32::MAX as i64
+);
+
+impl Clamp<i32> for u32 {
+    fn clamp(x: i32) -> u32 {
+        if x > 0 {
+            x as u32
+        } else {
+            0
+        }
+    }
+}
+
 implement_identity_clamp!(u8, i8, u16, i16, u32, i32, u64, i64, f32, f64);
 
 #[cfg(test)]
 mod tests {
-    use super::Clamp;
+    use super::{Clamp, Position, Score, ScoreExt};
+
+    struct Located(u32, u32);
+
+    impl Position for Located {
+        fn x(&self) -> u32 {
+            self.0
+        }
+        fn y(&self) -> u32 {
+            self.1
+        }
+    }
+
+    #[test]
+    fn test_pixel_distance() {
+        let p = Located(1, 2);
+        let q = Located(4, 6);
+        assert_eq!(p.pixel_distance(&q), 5.0);
+        assert_eq!(q.pixel_distance(&p), 5.0);
+        assert!(p.within(&q, 5.0));
+        assert!(!p.within(&q, 4.9));
+    }
+
+    #[test]
+    fn test_position_is_object_safe() {
+        let positions: Vec<Box<dyn Position>> = vec![Box::new(Located(1, 2))];
+        assert_eq!((positions[0].x(), positions[0].y()), (1, 2));
+    }
+
+    struct Scored(f32);
+
+    impl Score for Scored {
+        fn score(&self) -> f32 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_sorted_by_score_desc() {
+        let items = [Scored(1.0), Scored(f32::NAN), Scored(3.0), Scored(2.0)];
+        let scores: Vec<f32> = items
+            .sorted_by_score_desc()
+            .iter()
+            .map(|t| t.score())
+            .collect();
+        assert_eq!(scores[..3], [3.0, 2.0, 1.0]);
+        assert!(scores[3].is_nan());
+    }
+
+    #[test]
+    fn test_max_by_score() {
+        let items = [Scored(f32::NAN), Scored(1.0), Scored(3.0), Scored(3.0)];
+        assert!(std::ptr::eq(items.max_by_score().unwrap(), &items[2]));
+
+        let empty: [Scored; 0] = [];
+        assert!(empty.max_by_score().is_none());
+    }
+
+    #[test]
+    fn test_clamp_i32() {
+        assert_eq!(<u8 as Clamp<i32>>::clamp(-5), 0);
+        assert_eq!(<u8 as Clamp<i32>>::clamp(300), 255);
+        assert_eq!(<u16 as Clamp<i32>>::clamp(70000), 65535);
+        assert_eq!(<i16 as Clamp<i32>>::clamp(-40000), -32768);
+        assert_eq!(<u32 as Clamp<i32>>::clamp(-1), 0);
+        assert_eq!(<u32 as Clamp<i32>>::clamp(i32::MAX), i32::MAX as u32);
+    }
+
+    #[test]
+    fn test_clamp_i64() {
+        assert_eq!(<u8 as Clamp<i64>>::clamp(-5), 0);
+        assert_eq!(<u8 as Clamp<i64>>::clamp(100), 100);
+        assert_eq!(<u8 as Clamp<i64>>::clamp(300), 255);
+        assert_eq!(<u16 as Clamp<i64>>::clamp(-1), 0);
+        assert_eq!(<u16 as Clamp<i64>>::clamp(70000), 65535);
+        assert_eq!(<i16 as Clamp<i64>>::clamp(40000), 32767);
+        assert_eq!(<u32 as Clamp<i64>>::clamp(1 << 40), u32::MAX);
+        assert_eq!(<i32 as Clamp<i64>>::clamp(-(1 << 40)), i32::MIN);
+    }
 
     #[test]
     fn test_clamp_f32_u8() {
@@ -2,6 +2,7 @@ use crate::definitions::Image;
 use crate::drawing::draw_if_in_bounds;
 use crate::drawing::line::draw_line_segment_mut;
 use crate::drawing::Canvas;
+use crate::geometry::bresenham_circle_points;
 use image::{GenericImage, ImageBuffer};
 use std::f32;
 use std::i32;
@@ -191,34 +192,20 @@ where
 }
 
 /// Draw as much of a circle as lies inside the image bounds.
+///
+/// The pixels drawn are those given by [`bresenham_circle_points`], each drawn once.
+///
+/// [`bresenham_circle_points`]: ../geometry/fn.bresenham_circle_points.html
 pub fn draw_hollow_circle_mut<C>(canvas: &mut C, center: (i32, i32), radius: i32, color: C::Pixel)
 where
     C: Canvas,
     C::Pixel: 'static,
 {
-    let mut x = 0i32;
-    let mut y = radius;
-    let mut p = 1 - radius;
-    let x0 = center.0;
-    let y0 = center.1;
-
-    while x <= y {
-        draw_if_in_bounds(canvas, x0 + x, y0 + y, color);
-        draw_if_in_bounds(canvas, x0 + y, y0 + x, color);
-        draw_if_in_bounds(canvas, x0 - y, y0 + x, color);
-        draw_if_in_bounds(canvas, x0 - x, y0 + y, color);
-        draw_if_in_bounds(canvas, x0 - x, y0 - y, color);
-        draw_if_in_bounds(canvas, x0 - y, y0 - x, color);
-        draw_if_in_bounds(canvas, x0 + y, y0 - x, color);
-        draw_if_in_bounds(canvas, x0 + x, y0 - y, color);
-
-        x += 1;
-        if p < 0 {
-            p += 2 * x + 1;
-        } else {
-            y -= 1;
-            p += 2 * (x - y) + 1;
-        }
+    if radius < 0 {
+        return;
+    }
+    for (x, y) in bresenham_circle_points(radius as u32) {
+        draw_if_in_bounds(canvas, center.0 + x, center.1 + y, color);
     }
 }
 
@@ -291,6 +278,22 @@ where
 mod tests {
     use image::{GrayImage, Luma};
 
+    #[test]
+    fn test_draw_hollow_circle_partly_outside_image() {
+        use super::draw_hollow_circle_mut;
+
+        let mut image = GrayImage::new(5, 5);
+        draw_hollow_circle_mut(&mut image, (1, 2), 2, Luma([1u8]));
+
+        let expected = gray_image!(
+            1, 1, 1, 0, 0;
+            0, 0, 0, 1, 0;
+            0, 0, 0, 1, 0;
+            0, 0, 0, 1, 0;
+            1, 1, 1, 0, 0);
+        assert_pixels_eq!(image, expected);
+    }
+
     macro_rules! bench_hollow_ellipse {
         ($name:ident, $center:expr, $width_radius:expr, $height_radius:expr) => {
             #[bench]
@@ -1,6 +1,9 @@
-use crate::definitions::Image;
-use crate::drawing::Canvas;
-use image::{GenericImage, ImageBuffer};
+use crate::corners::Corner;
+use crate::definitions::{Clamp, Image};
+use crate::drawing::{draw_hollow_circle_mut, Canvas};
+use crate::math::cast;
+use conv::ValueInto;
+use image::{GenericImage, ImageBuffer, Pixel};
 use std::i32;
 
 /// Draws a colored cross on an image in place. Handles coordinates outside image bounds.
@@ -46,6 +49,114 @@ where
     out
 }
 
+/// Draws a marker at each of the given corners, e.g. those returned by
+/// [`corners_fast9`](../corners/fn.corners_fast9.html). Handles corners outside image bounds.
+///
+/// If `radius` is 0 each corner is drawn as a cross, as for [`draw_cross_mut`],
+/// otherwise it is drawn as a hollow circle of the given radius. All corners are drawn
+/// in `color`, whatever their score. To shade corners by score use
+/// [`draw_corners_scaled_mut`].
+///
+/// [`draw_cross_mut`]: fn.draw_cross_mut.html
+/// [`draw_corners_scaled_mut`]: fn.draw_corners_scaled_mut.html
+pub fn draw_corners_mut<C>(canvas: &mut C, corners: &[Corner], radius: u32, color: C::Pixel)
+where
+    C: Canvas,
+    C::Pixel: 'static,
+{
+    for corner in corners {
+        draw_corner_marker(canvas, corner, radius, color);
+    }
+}
+
+/// Draws a marker at each of the given corners, with brightness proportional to score.
+/// Handles corners outside image bounds.
+///
+/// Scores are normalised to lie in `[0, 1]` over `corners`, and each channel of `color`
+/// is multiplied by the normalised score of the corner being drawn. If all corners have
+/// the same score then they are all drawn in `color`. Markers are drawn as for
+/// [`draw_corners_mut`].
+///
+/// [`draw_corners_mut`]: fn.draw_corners_mut.html
+pub fn draw_corners_scaled_mut<C>(canvas: &mut C, corners: &[Corner], radius: u32, color: C::Pixel)
+where
+    C: Canvas,
+    C::Pixel: 'static,
+    <C::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let min = corners
+        .iter()
+        .map(|c| c.score)
+        .fold(f32::INFINITY, f32::min);
+    let max = corners
+        .iter()
+        .map(|c| c.score)
+        .fold(f32::NEG_INFINITY, f32::max);
+
+    for corner in corners {
+        let scale = if max > min {
+            (corner.score - min) / (max - min)
+        } else {
+            1.0
+        };
+        let scaled = color.map(|c| Clamp::clamp(cast(c) * scale));
+        draw_corner_marker(canvas, corner, radius, scaled);
+    }
+}
+
+/// Draws a marker at each of the given corners, with brightness proportional to score.
+/// Handles corners outside image bounds.
+///
+/// See [`draw_corners_scaled_mut`](fn.draw_corners_scaled_mut.html) for details.
+pub fn draw_corners_scaled<I>(
+    image: &I,
+    corners: &[Corner],
+    radius: u32,
+    color: I::Pixel,
+) -> Image<I::Pixel>
+where
+    I: GenericImage,
+    I::Pixel: 'static,
+    <I::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let mut out = ImageBuffer::new(image.width(), image.height());
+    out.copy_from(image, 0, 0).unwrap();
+    draw_corners_scaled_mut(&mut out, corners, radius, color);
+    out
+}
+
+fn draw_corner_marker<C>(canvas: &mut C, corner: &Corner, radius: u32, color: C::Pixel)
+where
+    C: Canvas,
+    C::Pixel: 'static,
+{
+    let (x, y) = (corner.x as i32, corner.y as i32);
+    if radius == 0 {
+        draw_cross_mut(canvas, color, x, y);
+    } else {
+        draw_hollow_circle_mut(canvas, (x, y), radius as i32, color);
+    }
+}
+
+/// Draws a marker at each of the given corners. Handles corners outside image bounds.
+///
+/// See [`draw_corners_mut`](fn.draw_corners_mut.html) for details.
+pub fn draw_corners<I>(
+    image: &I,
+    corners: &[Corner],
+    radius: u32,
+    color: I::Pixel,
+) -> Image<I::Pixel>
+where
+    I: GenericImage,
+    I::Pixel: 'static,
+{
+    let mut out = ImageBuffer::new(image.width(), image.height());
+    out.copy_from(image, 0, 0).unwrap();
+    draw_corners_mut(&mut out, corners, radius, color);
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -148,4 +259,58 @@ mod tests {
 
         assert_pixels_eq!(draw_cross(&image, Luma([9u8]), 5, 0), expected);
     }
+
+    #[test]
+    fn test_draw_corners() {
+        let image = GrayImage::from_pixel(7, 5, Luma([1u8]));
+        let corners = [Corner::new(1, 1, 10.0), Corner::new(5, 3, 0.5)];
+
+        let expected = gray_image!(
+            1, 2, 1, 1, 1, 1, 1;
+            2, 2, 2, 1, 1, 1, 1;
+            1, 2, 1, 1, 1, 2, 1;
+            1, 1, 1, 1, 2, 2, 2;
+            1, 1, 1, 1, 1, 2, 1);
+
+        assert_pixels_eq!(draw_corners(&image, &corners, 0, Luma([2u8])), expected);
+
+        let mut expected = image.clone();
+        draw_hollow_circle_mut(&mut expected, (1, 1), 2, Luma([2u8]));
+        draw_hollow_circle_mut(&mut expected, (5, 3), 2, Luma([2u8]));
+
+        assert_pixels_eq!(draw_corners(&image, &corners, 2, Luma([2u8])), expected);
+    }
+
+    #[test]
+    fn test_draw_corners_scaled() {
+        let image = GrayImage::from_pixel(7, 5, Luma([1u8]));
+        let corners = [
+            Corner::new(1, 1, 10.0),
+            Corner::new(5, 3, 0.0),
+            Corner::new(3, 4, 5.0),
+        ];
+
+        let expected = gray_image!(
+              1, 200,   1,   1,   1,   1,   1;
+            200, 200, 200,   1,   1,   1,   1;
+              1, 200,   1,   1,   1,   0,   1;
+              1,   1,   1, 100,   0,   0,   0;
+              1,   1, 100, 100, 100,   0,   1);
+
+        assert_pixels_eq!(
+            draw_corners_scaled(&image, &corners, 0, Luma([200u8])),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_draw_corners_scaled_equal_scores() {
+        let image = GrayImage::from_pixel(7, 5, Luma([1u8]));
+        let corners = [Corner::new(1, 1, 3.0), Corner::new(5, 3, 3.0)];
+
+        assert_pixels_eq!(
+            draw_corners_scaled(&image, &corners, 0, Luma([2u8])),
+            draw_corners(&image, &corners, 0, Luma([2u8]))
+        );
+    }
 }
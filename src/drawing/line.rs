@@ -1,81 +1,12 @@
 use crate::definitions::Image;
-use crate::drawing::Canvas;
+use crate::drawing::{draw_if_in_bounds, Canvas};
+use crate::geometry::BresenhamLineIter;
+use crate::point::Point;
 use image::{GenericImage, ImageBuffer, Pixel};
 use std::f32;
 use std::i32;
 use std::mem::{swap, transmute};
 
-/// Iterates over the coordinates in a line segment using
-/// [Bresenham's line drawing algorithm](https://en.wikipedia.org/wiki/Bresenham%27s_line_algorithm).
-pub struct BresenhamLineIter {
-    dx: f32,
-    dy: f32,
-    x: i32,
-    y: i32,
-    error: f32,
-    end_x: i32,
-    is_steep: bool,
-    y_step: i32,
-}
-
-impl BresenhamLineIter {
-    /// Creates a [`BresenhamLineIter`](struct.BresenhamLineIter.html) which will iterate over the integer coordinates
-    /// between `start` and `end`.
-    pub fn new(start: (f32, f32), end: (f32, f32)) -> BresenhamLineIter {
-        let (mut x0, mut y0) = (start.0, start.1);
-        let (mut x1, mut y1) = (end.0, end.1);
-
-        let is_steep = (y1 - y0).abs() > (x1 - x0).abs();
-        if is_steep {
-            swap(&mut x0, &mut y0);
-            swap(&mut x1, &mut y1);
-        }
-
-        if x0 > x1 {
-            swap(&mut x0, &mut x1);
-            swap(&mut y0, &mut y1);
-        }
-
-        let dx = x1 - x0;
-
-        BresenhamLineIter {
-            dx,
-            dy: (y1 - y0).abs(),
-            x: x0 as i32,
-            y: y0 as i32,
-            error: dx / 2f32,
-            end_x: x1 as i32,
-            is_steep,
-            y_step: if y0 < y1 { 1 } else { -1 },
-        }
-    }
-}
-
-impl Iterator for BresenhamLineIter {
-    type Item = (i32, i32);
-
-    fn next(&mut self) -> Option<(i32, i32)> {
-        if self.x > self.end_x {
-            None
-        } else {
-            let ret = if self.is_steep {
-                (self.y, self.x)
-            } else {
-                (self.x, self.y)
-            };
-
-            self.x += 1;
-            self.error -= self.dy;
-            if self.error < 0f32 {
-                self.y += self.y_step;
-                self.error += self.dx;
-            }
-
-            Some(ret)
-        }
-    }
-}
-
 fn clamp(x: f32, upper_bound: u32) -> f32 {
     if x < 0f32 {
         return 0f32;
@@ -205,6 +136,53 @@ where
     }
 }
 
+/// Draws as much of the polyline connecting consecutive `points` as lies inside the image
+/// bounds. If `closed` is `true` then a segment is also drawn from the last point back to
+/// the first.
+///
+/// A single point is drawn as a single pixel, and an empty slice draws nothing.
+pub fn draw_polyline<I>(
+    image: &I,
+    points: &[Point<i32>],
+    closed: bool,
+    color: I::Pixel,
+) -> Image<I::Pixel>
+where
+    I: GenericImage,
+    I::Pixel: 'static,
+{
+    let mut out = ImageBuffer::new(image.width(), image.height());
+    out.copy_from(image, 0, 0).unwrap();
+    draw_polyline_mut(&mut out, points, closed, color);
+    out
+}
+
+/// Draws as much of the polyline connecting consecutive `points` as lies inside the image
+/// bounds. If `closed` is `true` then a segment is also drawn from the last point back to
+/// the first.
+///
+/// A single point is drawn as a single pixel, and an empty slice draws nothing.
+pub fn draw_polyline_mut<C>(canvas: &mut C, points: &[Point<i32>], closed: bool, color: C::Pixel)
+where
+    C: Canvas,
+    C::Pixel: 'static,
+{
+    let to_f32 = |p: Point<i32>| (p.x as f32, p.y as f32);
+
+    match points.len() {
+        0 => {}
+        1 => draw_if_in_bounds(canvas, points[0].x, points[0].y, color),
+        n => {
+            for segment in points.windows(2) {
+                draw_line_segment_mut(canvas, to_f32(segment[0]), to_f32(segment[1]), color);
+            }
+            if closed && n > 2 {
+                draw_line_segment_mut(canvas, to_f32(points[n - 1]), to_f32(points[0]), color);
+            }
+        }
+    }
+}
+
 /// Draws as much of the line segment between start and end as lies inside the image bounds.
 /// The parameters of blend are (line color, original color, line weight).
 /// Consider using [`interpolate`](fn.interpolate.html) for blend.
@@ -334,6 +312,44 @@ mod tests {
     use super::*;
     use image::{GrayImage, Luma};
 
+    #[test]
+    fn test_draw_polyline_open_and_closed() {
+        let image = GrayImage::from_pixel(5, 5, Luma([1u8]));
+        let points = [Point::new(0, 0), Point::new(4, 0), Point::new(4, 4)];
+
+        let expected = gray_image!(
+            4, 4, 4, 4, 4;
+            1, 1, 1, 1, 4;
+            1, 1, 1, 1, 4;
+            1, 1, 1, 1, 4;
+            1, 1, 1, 1, 4);
+        assert_pixels_eq!(draw_polyline(&image, &points, false, Luma([4u8])), expected);
+
+        let expected = gray_image!(
+            4, 4, 4, 4, 4;
+            1, 4, 1, 1, 4;
+            1, 1, 4, 1, 4;
+            1, 1, 1, 4, 4;
+            1, 1, 1, 1, 4);
+        assert_pixels_eq!(draw_polyline(&image, &points, true, Luma([4u8])), expected);
+    }
+
+    #[test]
+    fn test_draw_polyline_degenerate() {
+        let image = GrayImage::from_pixel(3, 3, Luma([1u8]));
+        assert_pixels_eq!(draw_polyline(&image, &[], true, Luma([4u8])), image);
+
+        let expected = gray_image!(
+            1, 1, 1;
+            1, 4, 1;
+            1, 1, 1);
+        let single = [Point::new(1, 1)];
+        assert_pixels_eq!(draw_polyline(&image, &single, true, Luma([4u8])), expected);
+
+        let outside = [Point::new(5, 5)];
+        assert_pixels_eq!(draw_polyline(&image, &outside, false, Luma([4u8])), image);
+    }
+
     // As draw_line_segment is implemented in terms of BresenhamLineIter we
     // haven't bothered wriing any tests specifically for BresenhamLineIter itself.
 
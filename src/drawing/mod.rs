@@ -13,16 +13,22 @@ pub use self::conics::{
 };
 
 mod cross;
-pub use self::cross::{draw_cross, draw_cross_mut};
+pub use self::cross::{
+    draw_corners, draw_corners_mut, draw_corners_scaled, draw_corners_scaled_mut, draw_cross,
+    draw_cross_mut,
+};
 
 mod line;
 pub use self::line::{
     draw_antialiased_line_segment, draw_antialiased_line_segment_mut, draw_line_segment,
-    draw_line_segment_mut, BresenhamLineIter, BresenhamLinePixelIter, BresenhamLinePixelIterMut,
+    draw_line_segment_mut, draw_polyline, draw_polyline_mut, BresenhamLinePixelIter,
+    BresenhamLinePixelIterMut,
 };
 
+pub use crate::geometry::BresenhamLineIter;
+
 mod polygon;
-pub use self::polygon::{draw_polygon, draw_polygon_mut};
+pub use self::polygon::{draw_polygon, draw_polygon_mut, rasterize_polygon};
 
 mod rect;
 pub use self::rect::{
@@ -2,7 +2,7 @@ use crate::definitions::Image;
 use crate::drawing::line::draw_line_segment_mut;
 use crate::drawing::Canvas;
 use crate::point::Point;
-use image::{GenericImage, ImageBuffer};
+use image::{GenericImage, GrayImage, ImageBuffer, Luma};
 use std::cmp::{max, min};
 use std::f32;
 use std::i32;
@@ -108,3 +108,44 @@ where
         draw_line_segment_mut(canvas, start, end, color);
     }
 }
+
+/// Returns a mask of the given size whose pixels are 255 inside the polygon and 0 outside.
+///
+/// Pixels are considered inside exactly when [`draw_polygon_mut`] would set them, so
+/// the polygon's boundary is included. Unlike [`draw_polygon_mut`], this function does
+/// not panic if the last point of `poly` repeats the first: the repeated point is
+/// ignored. If `poly` is empty or has only a single distinct point then the returned
+/// mask is empty.
+///
+/// [`draw_polygon_mut`]: fn.draw_polygon_mut.html
+///
+/// # Examples
+/// ```
+/// # extern crate image;
+/// # #[macro_use]
+/// # extern crate imageproc;
+/// # fn main() {
+/// use imageproc::drawing::rasterize_polygon;
+/// use imageproc::point::Point;
+///
+/// let poly = [Point::new(1, 0), Point::new(3, 2), Point::new(1, 2)];
+///
+/// let expected = gray_image!(
+///     0, 255,   0,   0, 0;
+///     0, 255, 255,   0, 0;
+///     0, 255, 255, 255, 0);
+///
+/// assert_pixels_eq!(rasterize_polygon(5, 3, &poly), expected);
+/// # }
+/// ```
+pub fn rasterize_polygon(width: u32, height: u32, poly: &[Point<i32>]) -> GrayImage {
+    let mut mask = GrayImage::new(width, height);
+    let poly = match poly.split_last() {
+        Some((last, rest)) if !rest.is_empty() && *last == rest[0] => rest,
+        _ => poly,
+    };
+    if poly.len() > 1 {
+        draw_polygon_mut(&mut mask, poly, Luma([255u8]));
+    }
+    mask
+}
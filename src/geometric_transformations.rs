@@ -3,8 +3,10 @@
 
 use crate::definitions::{Clamp, Image};
 use crate::math::cast;
+use crate::point::Point;
+use crate::rect::{Rect, RotatedRect};
 use conv::ValueInto;
-use image::{GenericImageView, ImageBuffer, Pixel};
+use image::{GenericImageView, ImageBuffer, Luma, Pixel, Primitive};
 #[cfg(feature = "rayon")]
 use rayon::prelude::*;
 use std::{cmp, ops::Mul};
@@ -375,6 +377,292 @@ where
     out
 }
 
+/// Returns a copy of the region of `image` covered by `rect`.
+///
+/// `rect` is first intersected with the bounds of `image`, so the returned image
+/// may be smaller than `rect`. If `rect` does not overlap `image` at all then an
+/// empty image is returned.
+///
+/// # Examples
+/// ```
+/// # extern crate image;
+/// # #[macro_use]
+/// # extern crate imageproc;
+/// # fn main() {
+/// use imageproc::geometric_transformations::crop_to_rect;
+/// use imageproc::rect::Rect;
+///
+/// let image = gray_image!(
+///     1, 2, 3;
+///     4, 5, 6);
+///
+/// assert_pixels_eq!(
+///     crop_to_rect(&image, Rect::at(1, -1).of_size(5, 2)),
+///     gray_image!(2, 3));
+///
+/// assert_eq!(crop_to_rect(&image, Rect::at(3, 0).of_size(1, 1)).dimensions(), (0, 0));
+/// # }
+/// ```
+pub fn crop_to_rect<P>(image: &Image<P>, rect: Rect) -> Image<P>
+where
+    P: Pixel + 'static,
+{
+    let (width, height) = image.dimensions();
+    match rect.clamp_to(width, height) {
+        Some(r) => {
+            let (left, top) = (r.left() as u32, r.top() as u32);
+            ImageBuffer::from_fn(r.width(), r.height(), |x, y| {
+                *image.get_pixel(left + x, top + y)
+            })
+        }
+        None => ImageBuffer::new(0, 0),
+    }
+}
+
+/// How to choose the values of pixels added by [`pad_image`](fn.pad_image.html).
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum BorderMode<P> {
+    /// Set all border pixels to the given value.
+    Constant(P),
+    /// Mirror the image about its edges, repeating the edge pixels,
+    /// e.g. a row `abc` is padded as `cba|abc|cba`.
+    Reflect,
+    /// Repeat the nearest edge pixel, e.g. a row `abc` is padded as `aaa|abc|ccc`.
+    Replicate,
+    /// Tile the image, e.g. a row `abc` is padded as `abc|abc|abc`.
+    Wrap,
+}
+
+/// Returns a copy of `image` with `top`, `bottom`, `left` and `right` pixels added on
+/// each side, whose values are determined by `mode`.
+///
+/// # Panics
+///
+/// If `image` is empty, some padding is non-zero and `mode` is not `BorderMode::Constant`.
+///
+/// # Examples
+/// ```
+/// # extern crate image;
+/// # #[macro_use]
+/// # extern crate imageproc;
+/// # fn main() {
+/// use imageproc::geometric_transformations::{pad_image, BorderMode};
+///
+/// let image = gray_image!(
+///     1, 2, 3;
+///     4, 5, 6);
+///
+/// let reflected = gray_image!(
+///     2, 1, 1, 2, 3, 3;
+///     2, 1, 1, 2, 3, 3;
+///     5, 4, 4, 5, 6, 6);
+///
+/// assert_pixels_eq!(pad_image(&image, 1, 0, 2, 1, BorderMode::Reflect), reflected);
+/// # }
+/// ```
+pub fn pad_image<P>(
+    image: &Image<P>,
+    top: u32,
+    bottom: u32,
+    left: u32,
+    right: u32,
+    mode: BorderMode<P>,
+) -> Image<P>
+where
+    P: Pixel + 'static,
+{
+    let (width, height) = image.dimensions();
+    let out_width = width + left + right;
+    let out_height = height + top + bottom;
+
+    if let BorderMode::Constant(value) = mode {
+        let mut out = ImageBuffer::from_pixel(out_width, out_height, value);
+        for (x, y, p) in image.enumerate_pixels() {
+            out.put_pixel(x + left, y + top, *p);
+        }
+        return out;
+    }
+
+    assert!(
+        (width > 0 && height > 0) || (out_width == width && out_height == height),
+        "an empty image can only be padded using BorderMode::Constant"
+    );
+
+    let source_index = |i: u32, offset: u32, len: u32| -> u32 {
+        let (i, len) = (i as i64 - offset as i64, len as i64);
+        let i = match mode {
+            BorderMode::Reflect => {
+                let m = i.rem_euclid(2 * len);
+                if m < len {
+                    m
+                } else {
+                    2 * len - 1 - m
+                }
+            }
+            BorderMode::Replicate => i.max(0).min(len - 1),
+            BorderMode::Wrap => i.rem_euclid(len),
+            BorderMode::Constant(_) => unreachable!(),
+        };
+        i as u32
+    };
+
+    ImageBuffer::from_fn(out_width, out_height, |x, y| {
+        *image.get_pixel(source_index(x, left, width), source_index(y, top, height))
+    })
+}
+
+/// Returns a copy of `image` with `top`, `bottom`, `left` and `right` pixels removed from
+/// each side. This is the inverse of [`pad_image`] with the same margins.
+///
+/// [`pad_image`]: fn.pad_image.html
+///
+/// # Panics
+///
+/// If `left + right` exceeds the width of `image` or `top + bottom` exceeds its height.
+///
+/// # Examples
+/// ```
+/// # extern crate image;
+/// # #[macro_use]
+/// # extern crate imageproc;
+/// # fn main() {
+/// use imageproc::geometric_transformations::{pad_image, unpad_image, BorderMode};
+///
+/// let image = gray_image!(
+///     1, 2, 3;
+///     4, 5, 6);
+///
+/// let padded = pad_image(&image, 1, 2, 3, 4, BorderMode::Wrap);
+///
+/// assert_pixels_eq!(unpad_image(&padded, 1, 2, 3, 4), image);
+/// # }
+/// ```
+pub fn unpad_image<P>(image: &Image<P>, top: u32, bottom: u32, left: u32, right: u32) -> Image<P>
+where
+    P: Pixel + 'static,
+{
+    let (width, height) = image.dimensions();
+    let horizontal = left as u64 + right as u64;
+    let vertical = top as u64 + bottom as u64;
+    assert!(
+        horizontal <= width as u64,
+        "left and right margins ({} + {}) exceed image width {}",
+        left,
+        right,
+        width
+    );
+    assert!(
+        vertical <= height as u64,
+        "top and bottom margins ({} + {}) exceed image height {}",
+        top,
+        bottom,
+        height
+    );
+
+    ImageBuffer::from_fn(
+        width - horizontal as u32,
+        height - vertical as u32,
+        |x, y| *image.get_pixel(x + left, y + top),
+    )
+}
+
+/// Extracts the region of `image` covered by a rotated rectangle into an upright image.
+///
+/// The output has width `rect.width` and height `rect.height`, rounded to the nearest
+/// integer. The output pixel at `(0, 0)` is sampled from the corner of `rect` that would be
+/// its top left if it were rotated by `-rect.angle` about its centre, and the output rows
+/// are parallel to the rectangle's rotated x-axis. Samples are computed by bilinear
+/// interpolation, and any which do not lie within the image are set to `background`.
+pub fn crop_rotated_rect<P>(image: &Image<P>, rect: RotatedRect, background: P) -> Image<P>
+where
+    P: Pixel + 'static,
+    <P as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let width = rect.width.round().max(0.0) as u32;
+    let height = rect.height.round().max(0.0) as u32;
+    let (sin, cos) = rect.angle.sin_cos();
+
+    // Offsets of output pixels are measured from the centre of the output image.
+    let x_offset = (width as f32 - 1.0) / 2.0;
+    let y_offset = (height as f32 - 1.0) / 2.0;
+
+    ImageBuffer::from_fn(width, height, |x, y| {
+        let dx = x as f32 - x_offset;
+        let dy = y as f32 - y_offset;
+        let sx = rect.center.x + cos * dx - sin * dy;
+        let sy = rect.center.y + sin * dx + cos * dy;
+        interpolate_bilinear_inclusive(image, sx, sy).unwrap_or(background)
+    })
+}
+
+/// Samples the intensity of a grayscale image at `samples` equally spaced points on the
+/// line segment from `start` to `end`, using bilinear interpolation.
+///
+/// The first and last samples are taken at `start` and `end` respectively. If `samples`
+/// is 1 then the only sample is taken at `start`. Points outside the image are clamped
+/// to the nearest point within the image bounds before sampling.
+///
+/// # Panics
+///
+/// If `samples` is non-zero and `image` is empty.
+///
+/// # Examples
+/// ```
+/// # extern crate image;
+/// # #[macro_use]
+/// # extern crate imageproc;
+/// # fn main() {
+/// use imageproc::geometric_transformations::sample_line;
+/// use imageproc::point::Point;
+///
+/// let image = gray_image!(
+///     0, 10, 20;
+///     0, 10, 20);
+///
+/// assert_eq!(
+///     sample_line(&image, Point::new(0.0, 0.5), Point::new(2.0, 0.5), 5),
+///     vec![0.0, 5.0, 10.0, 15.0, 20.0]);
+/// # }
+/// ```
+pub fn sample_line<I, C>(image: &I, start: Point<f32>, end: Point<f32>, samples: usize) -> Vec<f32>
+where
+    I: GenericImageView<Pixel = Luma<C>>,
+    C: Primitive + ValueInto<f32> + 'static,
+{
+    if samples == 0 {
+        return Vec::new();
+    }
+    let (width, height) = image.dimensions();
+    assert!(width > 0 && height > 0, "image must be non-empty");
+
+    let value = |x: u32, y: u32| -> f32 { cast(image.get_pixel(x, y)[0]) };
+    let max_x = (width - 1) as f32;
+    let max_y = (height - 1) as f32;
+
+    (0..samples)
+        .map(|i| {
+            let t = if samples == 1 {
+                0.0
+            } else {
+                i as f32 / (samples - 1) as f32
+            };
+            let p = start.lerp(end, t);
+            let x = p.x.max(0.0).min(max_x);
+            let y = p.y.max(0.0).min(max_y);
+
+            // Uses the same pixels and weights as interpolate_bilinear, but blends in f32
+            // rather than rounding to the image's subpixel type. The point has been
+            // clamped to the image, so bilinear_neighbours always succeeds.
+            let ([left, top, right, bottom], right_weight, bottom_weight) =
+                bilinear_neighbours(width, height, x, y).unwrap();
+            let upper = (1.0 - right_weight) * value(left, top) + right_weight * value(right, top);
+            let lower =
+                (1.0 - right_weight) * value(left, bottom) + right_weight * value(right, bottom);
+            (1.0 - bottom_weight) * upper + bottom_weight * lower
+        })
+        .collect()
+}
+
 /// Applies a projective transformation to an image.
 ///
 /// The returned image has the same dimensions as `image`. Output pixels
@@ -414,9 +702,9 @@ pub fn warp_into<P>(
     <P as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32> + Sync,
 {
     let projection = projection.invert();
-    let nn = |x, y| interpolate_nearest(image, x, y, default);
-    let bl = |x, y| interpolate_bilinear(image, x, y, default);
-    let bc = |x, y| interpolate_bicubic(image, x, y, default);
+    let nn = |x, y| interpolate_nearest(image, x, y).unwrap_or(default);
+    let bl = |x, y| interpolate_bilinear(image, x, y).unwrap_or(default);
+    let bc = |x, y| interpolate_bicubic(image, x, y).unwrap_or(default);
     let wp = |x, y| projection.map_projective(x, y);
     let wa = |x, y| projection.map_affine(x, y);
     let wt = |x, y| projection.map_translation(x, y);
@@ -487,9 +775,9 @@ pub fn warp_into_with<P, F>(
     <P as Pixel>::Subpixel: Send + Sync,
     <P as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
 {
-    let nn = |x, y| interpolate_nearest(image, x, y, default);
-    let bl = |x, y| interpolate_bilinear(image, x, y, default);
-    let bc = |x, y| interpolate_bicubic(image, x, y, default);
+    let nn = |x, y| interpolate_nearest(image, x, y).unwrap_or(default);
+    let bl = |x, y| interpolate_bilinear(image, x, y).unwrap_or(default);
+    let bc = |x, y| interpolate_bicubic(image, x, y).unwrap_or(default);
     use Interpolation as I;
 
     match interpolation {
@@ -624,10 +912,16 @@ where
     outp
 }
 
-fn interpolate_bicubic<P>(image: &Image<P>, x: f32, y: f32, default: P) -> P
+/// Samples `image` at the location `(x, y)` by bicubic interpolation between the sixteen
+/// pixels closest to it, using the Catmull-Rom kernel.
+///
+/// Returns `None` unless `1 <= x < width - 3` and `1 <= y < height - 3`, which ensures
+/// that all sixteen of these pixels lie inside the image. Each channel is interpolated separately,
+/// and the results are clamped to the range of the image's subpixel type.
+pub fn interpolate_bicubic<I>(image: &I, x: f32, y: f32) -> Option<I::Pixel>
 where
-    P: Pixel + 'static,
-    <P as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+    I: GenericImageView,
+    <I::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
 {
     let left = x.floor() - 1f32;
     let right = left + 4f32;
@@ -637,14 +931,12 @@ where
     let x_weight = x - (left + 1f32);
     let y_weight = y - (top + 1f32);
 
-    let mut col: [P; 4] = [default, default, default, default];
-
     let (width, height) = image.dimensions();
     if left < 0f32 || right >= width as f32 || top < 0f32 || bottom >= height as f32 {
-        default
+        None
     } else {
-        for row in top as u32..bottom as u32 {
-            let (p0, p1, p2, p3): (P, P, P, P) = unsafe {
+        let row = |row: u32| {
+            let (p0, p1, p2, p3) = unsafe {
                 (
                     image.unsafe_get_pixel(left as u32, row),
                     image.unsafe_get_pixel(left as u32 + 1, row),
@@ -652,12 +944,12 @@ where
                     image.unsafe_get_pixel(left as u32 + 3, row),
                 )
             };
+            blend_cubic(&p0, &p1, &p2, &p3, x_weight)
+        };
+        let top = top as u32;
+        let col = [row(top), row(top + 1), row(top + 2), row(top + 3)];
 
-            let c = blend_cubic(&p0, &p1, &p2, &p3, x_weight);
-            col[row as usize - top as usize] = c;
-        }
-
-        blend_cubic(&col[0], &col[1], &col[2], &col[3], y_weight)
+        Some(blend_cubic(&col[0], &col[1], &col[2], &col[3], y_weight))
     }
 }
 
@@ -686,46 +978,103 @@ where
     })
 }
 
-fn interpolate_bilinear<P>(image: &Image<P>, x: f32, y: f32, default: P) -> P
+/// Samples `image` at the location `(x, y)` by bilinearly interpolating between the four
+/// pixels closest to it.
+///
+/// Returns `None` if any of these four pixels lies outside the image, i.e. unless
+/// `0 <= x < width - 1` and `0 <= y < height - 1`. Each channel is interpolated separately,
+/// and the results are clamped to the range of the image's subpixel type.
+///
+/// # Examples
+/// ```
+/// # extern crate image;
+/// # #[macro_use]
+/// # extern crate imageproc;
+/// # fn main() {
+/// use image::Luma;
+/// use imageproc::geometric_transformations::interpolate_bilinear;
+///
+/// let image = gray_image!(
+///     0, 10;
+///     20, 30);
+///
+/// assert_eq!(interpolate_bilinear(&image, 0.5, 0.5), Some(Luma([15])));
+/// assert_eq!(interpolate_bilinear(&image, 0.5, 1.0), None);
+/// # }
+/// ```
+pub fn interpolate_bilinear<I>(image: &I, x: f32, y: f32) -> Option<I::Pixel>
+where
+    I: GenericImageView,
+    <I::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+{
+    let (width, height) = image.dimensions();
+    if x < 0f32 || x.floor() + 1f32 >= width as f32 || y < 0f32 || y.floor() + 1f32 >= height as f32
+    {
+        return None;
+    }
+    interpolate_bilinear_inclusive(image, x, y)
+}
+
+/// As [`interpolate_bilinear`], but also samples the last row and column of the image,
+/// i.e. returns `None` unless `0 <= x <= width - 1` and `0 <= y <= height - 1`.
+///
+/// [`interpolate_bilinear`]: fn.interpolate_bilinear.html
+fn interpolate_bilinear_inclusive<I>(image: &I, x: f32, y: f32) -> Option<I::Pixel>
 where
-    P: Pixel + 'static,
-    <P as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
+    I: GenericImageView,
+    <I::Pixel as Pixel>::Subpixel: ValueInto<f32> + Clamp<f32>,
 {
-    let left = x.floor();
-    let right = left + 1f32;
-    let top = y.floor();
-    let bottom = top + 1f32;
+    let (width, height) = image.dimensions();
+    let ([left, top, right, bottom], right_weight, bottom_weight) =
+        bilinear_neighbours(width, height, x, y)?;
 
-    let right_weight = x - left;
-    let bottom_weight = y - top;
+    let (tl, tr, bl, br) = unsafe {
+        (
+            image.unsafe_get_pixel(left, top),
+            image.unsafe_get_pixel(right, top),
+            image.unsafe_get_pixel(left, bottom),
+            image.unsafe_get_pixel(right, bottom),
+        )
+    };
+    Some(blend_bilinear(tl, tr, bl, br, right_weight, bottom_weight))
+}
 
-    // default if out of bound
-    let (width, height) = image.dimensions();
-    if left < 0f32 || right >= width as f32 || top < 0f32 || bottom >= height as f32 {
-        default
-    } else {
-        let (tl, tr, bl, br) = unsafe {
-            (
-                image.unsafe_get_pixel(left as u32, top as u32),
-                image.unsafe_get_pixel(right as u32, top as u32),
-                image.unsafe_get_pixel(left as u32, bottom as u32),
-                image.unsafe_get_pixel(right as u32, bottom as u32),
-            )
-        };
-        blend_bilinear(tl, tr, bl, br, right_weight, bottom_weight)
+/// Returns the columns and rows `[left, top, right, bottom]` of the four pixels used to
+/// bilinearly interpolate an image of the given size at `(x, y)`, and the weights of the
+/// right column and bottom row. Returns `None` unless `0 <= x <= width - 1` and
+/// `0 <= y <= height - 1`.
+fn bilinear_neighbours(width: u32, height: u32, x: f32, y: f32) -> Option<([u32; 4], f32, f32)> {
+    if x < 0f32 || x > width as f32 - 1f32 || y < 0f32 || y > height as f32 - 1f32 {
+        return None;
     }
+
+    let left = x.floor();
+    let top = y.floor();
+    // On the last row or column the second neighbour has zero weight, so
+    // we reuse the first rather than reading outside the image.
+    let right = (left + 1f32).min(width as f32 - 1f32);
+    let bottom = (top + 1f32).min(height as f32 - 1f32);
+
+    Some((
+        [left as u32, top as u32, right as u32, bottom as u32],
+        x - left,
+        y - top,
+    ))
 }
 
+/// Samples `image` at the pixel closest to the location `(x, y)`.
+///
+/// Returns `None` if this pixel lies outside the image.
 #[inline(always)]
-fn interpolate_nearest<P: Pixel + 'static>(image: &Image<P>, x: f32, y: f32, default: P) -> P {
+pub fn interpolate_nearest<I: GenericImageView>(image: &I, x: f32, y: f32) -> Option<I::Pixel> {
     let rx = x.round();
     let ry = y.round();
 
     let (width, height) = image.dimensions();
     if rx < 0f32 || rx >= width as f32 || ry < 0f32 || ry >= height as f32 {
-        default
+        None
     } else {
-        unsafe { image.unsafe_get_pixel(rx as u32, ry as u32) }
+        Some(unsafe { image.unsafe_get_pixel(rx as u32, ry as u32) })
     }
 }
 
@@ -747,9 +1096,135 @@ pub enum Interpolation {
 mod tests {
     use super::*;
     use crate::utils::gray_bench_image;
-    use image::{GrayImage, Luma};
+    use image::{GrayImage, Luma, Rgb};
     use test::{black_box, Bencher};
 
+    #[test]
+    fn test_sample_line_clamps_to_image() {
+        let image = gray_image!(
+            0, 10, 20;
+            30, 40, 50);
+
+        assert_eq!(
+            sample_line(&image, Point::new(-2.0, 0.0), Point::new(4.0, 0.0), 4),
+            vec![0.0, 0.0, 20.0, 20.0]
+        );
+        assert_eq!(
+            sample_line(&image, Point::new(1.0, -1.0), Point::new(1.0, 2.0), 4),
+            vec![10.0, 10.0, 40.0, 40.0]
+        );
+        assert_eq!(
+            sample_line(&image, Point::new(0.5, 0.5), Point::new(2.0, 1.0), 1),
+            vec![20.0]
+        );
+        assert!(sample_line(&image, Point::new(0.0, 0.0), Point::new(1.0, 1.0), 0).is_empty());
+    }
+
+    #[test]
+    fn test_sample_line_matches_interpolate_bilinear() {
+        let bench = gray_bench_image(9, 7);
+        let image: Image<Luma<f32>> =
+            ImageBuffer::from_fn(9, 7, |x, y| Luma([bench.get_pixel(x, y)[0] as f32]));
+        let (start, end) = (Point::new(0.3, 5.9), Point::new(7.6, 0.2));
+        let samples = sample_line(&image, start, end, 11);
+        for (i, sample) in samples.into_iter().enumerate() {
+            let p = start.lerp(end, i as f32 / 10.0);
+            let expected = interpolate_bilinear(&image, p.x, p.y).unwrap()[0];
+            assert!((sample - expected).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_interpolate_bilinear_rgb() {
+        let image = rgb_image!(
+            [0, 100, 255], [10, 200, 255];
+            [20, 100, 255], [30, 200, 255]);
+
+        assert_eq!(
+            interpolate_bilinear(&image, 0.5, 0.25),
+            Some(Rgb([10, 150, 255]))
+        );
+        assert_eq!(
+            interpolate_bilinear(&image, 0.0, 0.0),
+            Some(Rgb([0, 100, 255]))
+        );
+        assert_eq!(interpolate_bilinear(&image, -0.5, 0.0), None);
+        assert_eq!(interpolate_bilinear(&image, 0.0, 1.5), None);
+    }
+
+    #[test]
+    fn test_crop_rotated_rect() {
+        let image = gray_image!(
+             1,  2,  3,  4;
+             5,  6,  7,  8;
+             9, 10, 11, 12;
+            13, 14, 15, 16);
+
+        // An axis-aligned rectangle is equivalent to an ordinary crop.
+        let rect = RotatedRect {
+            center: Point::new(1.5, 1.0),
+            width: 2.0,
+            height: 3.0,
+            angle: 0.0,
+        };
+        let expected = gray_image!(
+             2,  3;
+             6,  7;
+            10, 11);
+        assert_pixels_eq!(crop_rotated_rect(&image, rect, Luma([0])), expected);
+
+        // After a quarter turn the rectangle's x-axis points down the image. The
+        // angle is inexact, so allow for rounding of the interpolated values.
+        let rect = RotatedRect {
+            center: Point::new(1.5, 1.5),
+            width: 2.0,
+            height: 2.0,
+            angle: std::f32::consts::FRAC_PI_2,
+        };
+        let expected = gray_image!(
+            7, 11;
+            6, 10);
+        assert_pixels_eq_within!(crop_rotated_rect(&image, rect, Luma([0])), expected, 1);
+    }
+
+    #[test]
+    fn test_crop_rotated_rect_background() {
+        let image = GrayImage::from_pixel(3, 3, Luma([7]));
+        let rect = RotatedRect {
+            center: Point::new(2.0, 1.0),
+            width: 3.0,
+            height: 1.0,
+            angle: 0.0,
+        };
+        assert_pixels_eq!(
+            crop_rotated_rect(&image, rect, Luma([0])),
+            gray_image!(7, 7, 0)
+        );
+    }
+
+    #[test]
+    fn test_interpolate_nearest() {
+        let image = gray_image!(
+            0, 10;
+            20, 30);
+
+        assert_eq!(interpolate_nearest(&image, 0.4, 0.6), Some(Luma([20])));
+        assert_eq!(interpolate_nearest(&image, 1.4, 1.4), Some(Luma([30])));
+        assert_eq!(interpolate_nearest(&image, 1.6, 0.0), None);
+        assert_eq!(interpolate_nearest(&image, -0.6, 0.0), None);
+    }
+
+    #[test]
+    fn test_interpolate_bicubic() {
+        // Catmull-Rom interpolation reproduces linear functions exactly.
+        let image = GrayImage::from_fn(6, 7, |x, y| Luma([(10 * x + 20 * y) as u8]));
+
+        assert_eq!(interpolate_bicubic(&image, 1.0, 2.0), Some(Luma([50])));
+        assert_eq!(interpolate_bicubic(&image, 1.5, 1.25), Some(Luma([40])));
+        assert_eq!(interpolate_bicubic(&image, 0.5, 1.0), None);
+        assert_eq!(interpolate_bicubic(&image, 1.0, 4.0), None);
+    }
+
     #[test]
     fn test_rotate_nearest_zero_radians() {
         let image = gray_image!(
@@ -799,6 +1274,79 @@ mod tests {
         assert_pixels_eq!(rotated, expected);
     }
 
+    #[test]
+    fn test_pad_image_border_modes() {
+        let image = gray_image!(
+            1, 2, 3;
+            4, 5, 6);
+
+        let constant = gray_image!(
+            0, 0, 0, 0, 0;
+            0, 1, 2, 3, 0;
+            0, 4, 5, 6, 0;
+            0, 0, 0, 0, 0);
+        assert_pixels_eq!(
+            pad_image(&image, 1, 1, 1, 1, BorderMode::Constant(Luma([0]))),
+            constant
+        );
+
+        let replicated = gray_image!(
+            1, 1, 1, 2, 3;
+            4, 4, 4, 5, 6;
+            4, 4, 4, 5, 6;
+            4, 4, 4, 5, 6);
+        assert_pixels_eq!(
+            pad_image(&image, 0, 2, 2, 0, BorderMode::Replicate),
+            replicated
+        );
+
+        let wrapped = gray_image!(
+            6, 4, 5, 6, 4, 5, 6, 4;
+            3, 1, 2, 3, 1, 2, 3, 1;
+            6, 4, 5, 6, 4, 5, 6, 4;
+            3, 1, 2, 3, 1, 2, 3, 1);
+        assert_pixels_eq!(pad_image(&image, 1, 1, 1, 4, BorderMode::Wrap), wrapped);
+
+        // Padding by more than the image size reflects repeatedly.
+        let single = gray_image!(1, 2);
+        assert_pixels_eq!(
+            pad_image(&single, 0, 0, 3, 3, BorderMode::Reflect),
+            gray_image!(2, 2, 1, 1, 2, 2, 1, 1)
+        );
+
+        assert_pixels_eq!(pad_image(&image, 0, 0, 0, 0, BorderMode::Wrap), image);
+    }
+
+    #[test]
+    fn test_unpad_image() {
+        let image = gray_image!(
+            1, 2, 3;
+            4, 5, 6);
+
+        assert_pixels_eq!(unpad_image(&image, 1, 0, 0, 2), gray_image!(4));
+        assert_eq!(unpad_image(&image, 0, 0, 1, 2).dimensions(), (0, 2));
+    }
+
+    #[test]
+    #[should_panic(expected = "exceed image height")]
+    fn test_unpad_image_rejects_large_margins() {
+        let image = GrayImage::new(5, 3);
+        let _ = unpad_image(&image, 2, 2, 0, 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_pad_empty_image_with_replicate() {
+        let _ = pad_image(
+            &GrayImage::new(0, 0),
+            1,
+            1,
+            1,
+            1,
+            BorderMode::<Luma<u8>>::Replicate,
+        );
+    }
+
     #[bench]
     fn bench_rotate_nearest(b: &mut Bencher) {
         let image = GrayImage::from_pixel(200, 200, Luma([15u8]));
@@ -1024,6 +1572,35 @@ mod tests {
         assert_pixels_eq!(translated_bilinear, expected);
     }
 
+    #[test]
+    fn test_warp_bilinear_last_row_and_column() {
+        let image = gray_image!(
+            1, 2, 3;
+            4, 5, 6;
+            7, 8, 9);
+
+        // Bilinear interpolation needs a pixel to the right of and below each sample,
+        // so the last row and column are set to the default.
+        let expected = gray_image!(
+            1, 2, 0;
+            4, 5, 0;
+            0, 0, 0);
+
+        let identity = Projection::translate(0.0, 0.0);
+        let warped = warp(&image, &identity, Interpolation::Bilinear, Luma([0u8]));
+        assert_pixels_eq!(warped, expected);
+
+        let mut out = GrayImage::new(3, 3);
+        warp_into(
+            &image,
+            &identity,
+            Interpolation::Bilinear,
+            Luma([0u8]),
+            &mut out,
+        );
+        assert_pixels_eq!(out, expected);
+    }
+
     #[test]
     fn test_affine_bicubic() {
         let image = gray_image!(
@@ -1,9 +1,11 @@
 //! Computational geometry functions, for example finding convex hulls.
 
-use crate::point::{distance, Line, Point, Rotation};
-use num::{cast, NumCast};
+use crate::point::{centroid, covariance, distance, Line, Point, Rotation};
+use crate::rect::RotatedRect;
+use num::NumCast;
 use std::cmp::{Ord, Ordering};
 use std::f64::{self, consts::PI};
+use std::mem::swap;
 
 /// Computes the length of an arc. If `closed` is set to `true` then the distance
 /// between the last and the first point is included in the total length.
@@ -20,8 +22,69 @@ where
     length
 }
 
+/// Resamples a polyline to `n` points spaced at equal arc length intervals.
+///
+/// If `closed` is `false` then the first and last output points are the first and last
+/// input points. If `closed` is `true` then the segment from the last input point back to
+/// the first is included, and the `n` output points are evenly spaced around the whole
+/// closed curve, starting at the first input point.
+///
+/// Returns an empty `Vec` if `poly` is empty or `n` is zero.
+pub fn resample_contour<T>(poly: &[Point<T>], n: usize, closed: bool) -> Vec<Point<f64>>
+where
+    T: NumCast + Copy,
+{
+    if poly.is_empty() || n == 0 {
+        return Vec::new();
+    }
+
+    let mut vertices: Vec<Point<f64>> = poly.iter().map(|p| p.to_f64()).collect();
+    if closed {
+        vertices.push(vertices[0]);
+    }
+
+    let total_length = arc_length(&vertices, false);
+    if n == 1 || total_length == 0.0 {
+        return vec![vertices[0]; n];
+    }
+
+    let spacing = if closed {
+        total_length / n as f64
+    } else {
+        total_length / (n - 1) as f64
+    };
+
+    let mut resampled = Vec::with_capacity(n);
+    let mut segment = 0;
+    let mut segment_start = 0.0;
+
+    for i in 0..n {
+        let target = i as f64 * spacing;
+        let mut segment_length = distance(vertices[segment], vertices[segment + 1]);
+        while segment_start + segment_length < target && segment + 2 < vertices.len() {
+            segment_start += segment_length;
+            segment += 1;
+            segment_length = distance(vertices[segment], vertices[segment + 1]);
+        }
+
+        let (p, q) = (vertices[segment], vertices[segment + 1]);
+        let t = if segment_length > 0.0 {
+            ((target - segment_start) / segment_length).min(1.0)
+        } else {
+            0.0
+        };
+        resampled.push(Point::new(p.x + t * (q.x - p.x), p.y + t * (q.y - p.y)));
+    }
+
+    resampled
+}
+
 /// Approximates a polygon using the [Douglas–Peucker algorithm].
 ///
+/// Points may have integer or floating point coordinates, so sub-pixel curves such as those
+/// produced by marching squares can be simplified directly. Returned points are taken from
+/// `curve` unchanged.
+///
 /// [Douglas–Peucker algorithm]: https://en.wikipedia.org/wiki/Ramer-Douglas-Peucker_algorithm
 pub fn approximate_polygon_dp<T>(curve: &[Point<T>], epsilon: f64, closed: bool) -> Vec<Point<T>>
 where
@@ -68,6 +131,745 @@ where
     res
 }
 
+/// Simplifies a closed polygon using the [Visvalingam–Whyatt algorithm], removing vertices
+/// until the total area removed would exceed `total_removed_area`, in square pixels.
+///
+/// At each step the vertex which forms the triangle of smallest area with its two current
+/// neighbours is removed, provided that the sum of the areas of all triangles removed so far
+/// would not exceed `total_removed_area`. This bounds how much the polygon changes overall,
+/// rather than how much it changes at each vertex as for [`approximate_polygon_dp`].
+/// At least three vertices are always kept. Returned points are taken from `poly` unchanged
+/// and are in their original order.
+///
+/// This runs in `O(n^2)` time for a polygon with `n` vertices.
+///
+/// # Examples
+/// ```
+/// use imageproc::geometry::simplify_to_area_budget;
+/// use imageproc::point::Point;
+///
+/// // Removing the vertex at (4, 0) removes a triangle of area 2.
+/// let poly = [
+///     Point::new(0, 0),
+///     Point::new(4, 0),
+///     Point::new(8, 1),
+///     Point::new(8, 8),
+///     Point::new(0, 8),
+/// ];
+/// assert_eq!(simplify_to_area_budget(&poly, 1.0).len(), 5);
+/// assert_eq!(
+///     simplify_to_area_budget(&poly, 2.0),
+///     vec![Point::new(0, 0), Point::new(8, 1), Point::new(8, 8), Point::new(0, 8)]
+/// );
+/// ```
+///
+/// [Visvalingam–Whyatt algorithm]: https://en.wikipedia.org/wiki/Visvalingam%E2%80%93Whyatt_algorithm
+/// [`approximate_polygon_dp`]: fn.approximate_polygon_dp.html
+pub fn simplify_to_area_budget<T>(poly: &[Point<T>], total_removed_area: f64) -> Vec<Point<T>>
+where
+    T: NumCast + Copy,
+{
+    let n = poly.len();
+    if n <= 3 {
+        return poly.to_vec();
+    }
+
+    let vertices: Vec<Point<f64>> = poly.iter().map(|p| p.to_f64()).collect();
+    let mut prev: Vec<usize> = (0..n).map(|i| (i + n - 1) % n).collect();
+    let mut next: Vec<usize> = (0..n).map(|i| (i + 1) % n).collect();
+    let mut removed = vec![false; n];
+
+    let triangle_area = |prev: &[usize], next: &[usize], i: usize| {
+        let (a, b, c) = (vertices[prev[i]], vertices[i], vertices[next[i]]);
+        ((b.x - a.x) * (c.y - a.y) - (c.x - a.x) * (b.y - a.y)).abs() / 2.0
+    };
+    let mut areas: Vec<f64> = (0..n).map(|i| triangle_area(&prev, &next, i)).collect();
+
+    let mut remaining = n;
+    let mut total = 0.0;
+    while remaining > 3 {
+        let (i, area) = (0..n)
+            .filter(|&i| !removed[i])
+            .map(|i| (i, areas[i]))
+            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal))
+            .unwrap();
+        if total + area > total_removed_area {
+            break;
+        }
+
+        total += area;
+        removed[i] = true;
+        remaining -= 1;
+        let (p, q) = (prev[i], next[i]);
+        next[p] = q;
+        prev[q] = p;
+        areas[p] = triangle_area(&prev, &next, p);
+        areas[q] = triangle_area(&prev, &next, q);
+    }
+
+    poly.iter()
+        .zip(removed)
+        .filter(|(_, removed)| !removed)
+        .map(|(p, _)| *p)
+        .collect()
+}
+
+/// The largest ratio between the length of a miter join produced by [`offset_polygon`]
+/// and the offset distance. Sharper corners are beveled instead.
+///
+/// [`offset_polygon`]: fn.offset_polygon.html
+pub const OFFSET_MITER_LIMIT: f64 = 2.0;
+
+/// Offsets a closed polygon by moving each of its edges `distance` along its normal.
+///
+/// A positive `distance` moves edges outward, inflating the polygon, and a negative
+/// `distance` moves them inward, deflating it. This is independent of the winding of
+/// the input polygon. Adjacent offset edges are joined by extending them until they meet
+/// (a miter join), unless the join would extend further than [`OFFSET_MITER_LIMIT`] times
+/// `|distance|` from the original vertex, in which case the corner is beveled and two
+/// output points are produced instead of one.
+///
+/// Consecutive duplicate points are ignored. Polygons with fewer than three distinct
+/// vertices are returned unchanged. No attempt is made to remove self-intersections,
+/// which can occur when deflating by more than the polygon's local thickness.
+///
+/// [`OFFSET_MITER_LIMIT`]: constant.OFFSET_MITER_LIMIT.html
+pub fn offset_polygon<T>(poly: &[Point<T>], distance: f64) -> Vec<Point<f64>>
+where
+    T: NumCast + Copy,
+{
+    let mut vertices: Vec<Point<f64>> = poly.iter().map(|p| p.to_f64()).collect();
+    vertices.dedup();
+    while vertices.len() > 1 && vertices.first() == vertices.last() {
+        vertices.pop();
+    }
+
+    let n = vertices.len();
+    if n < 3 || distance == 0.0 {
+        return vertices;
+    }
+
+    // Twice the signed area, positive if the vertices are counter-clockwise.
+    let signed_area: f64 = (0..n)
+        .map(|i| {
+            let (p, q) = (vertices[i], vertices[(i + 1) % n]);
+            p.x * q.y - q.x * p.y
+        })
+        .sum();
+    let sign = if signed_area >= 0.0 { 1.0 } else { -1.0 };
+
+    // Unit outward normal of the edge from vertices[i] to vertices[i + 1].
+    let normal = |i: usize| {
+        let edge = vertices[(i + 1) % n] - vertices[i];
+        let length = (edge.x * edge.x + edge.y * edge.y).sqrt();
+        Point::new(sign * edge.y / length, -sign * edge.x / length)
+    };
+
+    let mut offset = Vec::with_capacity(n);
+    for i in 0..n {
+        let p = vertices[i];
+        let n1 = normal((i + n - 1) % n);
+        let n2 = normal(i);
+        let cos_theta = n1.x * n2.x + n1.y * n2.y;
+
+        // The miter point is at distance |distance| / cos(theta / 2) from p,
+        // where theta is the angle between the normals, and
+        // cos(theta / 2) = sqrt((1 + cos(theta)) / 2).
+        if 1.0 + cos_theta > 2.0 / (OFFSET_MITER_LIMIT * OFFSET_MITER_LIMIT) {
+            let scale = distance / (1.0 + cos_theta);
+            offset.push(Point::new(
+                p.x + scale * (n1.x + n2.x),
+                p.y + scale * (n1.y + n2.y),
+            ));
+        } else {
+            offset.push(Point::new(p.x + distance * n1.x, p.y + distance * n1.y));
+            offset.push(Point::new(p.x + distance * n2.x, p.y + distance * n2.y));
+        }
+    }
+
+    offset
+}
+
+/// Computes the centroid of the region enclosed by a closed polygon.
+///
+/// This is the centre of mass of the polygon's interior, not the mean of its vertices,
+/// so is unaffected by how densely each part of the boundary is sampled. If the polygon
+/// encloses zero area, for example because all of its vertices are collinear, the mean
+/// of its vertices is returned instead.
+///
+/// # Panics
+///
+/// If `poly` is empty.
+pub fn polygon_centroid<T>(poly: &[Point<T>]) -> Point<f64>
+where
+    T: NumCast + Copy,
+{
+    assert!(!poly.is_empty(), "polygon must be non-empty");
+
+    // Work relative to the first vertex to reduce rounding errors for polygons
+    // far from the origin.
+    let origin = poly[0].to_f64();
+    let vertices: Vec<Point<f64>> = poly.iter().map(|p| p.to_f64() - origin).collect();
+    let n = vertices.len();
+
+    let (mut area, mut cx, mut cy) = (0.0, 0.0, 0.0);
+    for i in 0..n {
+        let (p, q) = (vertices[i], vertices[(i + 1) % n]);
+        let cross = p.x * q.y - q.x * p.y;
+        area += cross;
+        cx += (p.x + q.x) * cross;
+        cy += (p.y + q.y) * cross;
+    }
+
+    if area == 0.0 {
+        return centroid(poly);
+    }
+
+    Point::new(origin.x + cx / (3.0 * area), origin.y + cy / (3.0 * area))
+}
+
+/// Splits a simple polygon into triangles using ear clipping.
+///
+/// The polygon must be simple, i.e. its edges must not cross or touch except at shared
+/// vertices, but it need not be convex, and its vertices may be listed in either direction.
+/// Each returned triangle has the same winding as `poly`, and together they cover its interior
+/// without overlapping. A polygon with `n` vertices gives at most `n - 2` triangles, none of
+/// which have zero area. Vertices at which the boundary does not turn, including repeated
+/// consecutive vertices, are skipped once they are reached, so do not produce triangles of
+/// their own.
+///
+/// If `poly` is not simple then the triangles returned are unspecified and need not cover
+/// the polygon. If `poly` has fewer than three vertices or encloses zero area then an empty
+/// `Vec` is returned.
+///
+/// This runs in `O(n^3)` time in the worst case.
+///
+/// # Examples
+/// ```
+/// use imageproc::geometry::triangulate_polygon;
+/// use imageproc::point::Point;
+///
+/// // An L-shaped polygon.
+/// let poly = [
+///     Point::new(0, 0),
+///     Point::new(2, 0),
+///     Point::new(2, 1),
+///     Point::new(1, 1),
+///     Point::new(1, 2),
+///     Point::new(0, 2),
+/// ];
+/// assert_eq!(triangulate_polygon(&poly).len(), 4);
+/// ```
+pub fn triangulate_polygon<T>(poly: &[Point<T>]) -> Vec<[Point<T>; 3]>
+where
+    T: NumCast + Copy,
+{
+    let mut triangles = Vec::new();
+    if poly.len() < 3 {
+        return triangles;
+    }
+
+    // A convex vertex turns in the same direction as the polygon as a whole.
+    let area = signed_area(poly);
+    let convex = if area > 0.0 {
+        Orientation::CounterClockwise
+    } else if area < 0.0 {
+        Orientation::Clockwise
+    } else {
+        return triangles;
+    };
+    let reflex = if convex == Orientation::Clockwise {
+        Orientation::CounterClockwise
+    } else {
+        Orientation::Clockwise
+    };
+
+    let mut remaining: Vec<usize> = (0..poly.len()).collect();
+    let mut i = 0;
+    // Number of consecutive vertices examined without clipping an ear.
+    let mut failures = 0;
+
+    while remaining.len() > 2 && failures < remaining.len() {
+        let n = remaining.len();
+        let (a, b, c) = (
+            poly[remaining[(i + n - 1) % n]],
+            poly[remaining[i % n]],
+            poly[remaining[(i + 1) % n]],
+        );
+
+        let turn = orientation(a, b, c);
+        if turn == Orientation::Collinear {
+            remaining.remove(i % n);
+            failures = 0;
+            continue;
+        }
+
+        let is_ear = turn == convex
+            && remaining.iter().all(|&j| {
+                let p = poly[j];
+                let is_corner = |q: Point<T>| p.to_f64() == q.to_f64();
+                is_corner(a)
+                    || is_corner(b)
+                    || is_corner(c)
+                    || orientation(a, b, p) == reflex
+                    || orientation(b, c, p) == reflex
+                    || orientation(c, a, p) == reflex
+            });
+
+        if is_ear {
+            triangles.push([a, b, c]);
+            remaining.remove(i % n);
+            failures = 0;
+        } else {
+            i = (i + 1) % n;
+            failures += 1;
+        }
+    }
+
+    triangles
+}
+
+/// Twice the signed area of a closed polygon, which is positive if the polygon is
+/// counter-clockwise in a coordinate system whose y-axis points upwards.
+fn signed_area<T: NumCast + Copy>(poly: &[Point<T>]) -> f64 {
+    let n = poly.len();
+    (0..n)
+        .map(|i| {
+            let (p, q) = (poly[i].to_f64(), poly[(i + 1) % n].to_f64());
+            p.x * q.y - q.x * p.y
+        })
+        .sum()
+}
+
+/// Computes the distance from each vertex of a closed polygon to the polygon's centroid,
+/// as computed by [`polygon_centroid`].
+///
+/// The output has one entry per vertex, in the same order as `poly`. This signature is
+/// invariant to rotation and translation of the polygon, and is commonly used as the input
+/// to Fourier shape descriptors.
+///
+/// Returns an empty `Vec` if `poly` is empty.
+///
+/// [`polygon_centroid`]: fn.polygon_centroid.html
+pub fn centroid_distance_signature<T>(poly: &[Point<T>]) -> Vec<f64>
+where
+    T: NumCast + Copy,
+{
+    if poly.is_empty() {
+        return Vec::new();
+    }
+    let c = polygon_centroid(poly);
+    poly.iter().map(|p| distance(p.to_f64(), c)).collect()
+}
+
+/// Finds the point on the boundary of a closed polygon which is closest to `point`.
+///
+/// Returns the closest point, the index `i` of the edge on which it lies, and its distance
+/// from `point`. Edge `i` runs from `poly[i]` to `poly[(i + 1) % poly.len()]`. If several
+/// edges are equally close then the one with the smallest index is returned.
+///
+/// # Panics
+///
+/// If `poly` is empty.
+///
+/// # Examples
+/// ```
+/// use imageproc::geometry::closest_point_on_polygon;
+/// use imageproc::point::Point;
+///
+/// let square = [Point::new(0, 0), Point::new(4, 0), Point::new(4, 4), Point::new(0, 4)];
+///
+/// let (closest, edge, distance) = closest_point_on_polygon(Point::new(6, 1), &square);
+/// assert_eq!(closest, Point::new(4.0, 1.0));
+/// assert_eq!(edge, 1);
+/// assert_eq!(distance, 2.0);
+/// ```
+pub fn closest_point_on_polygon<T>(point: Point<T>, poly: &[Point<T>]) -> (Point<f64>, usize, f64)
+where
+    T: NumCast + Copy,
+{
+    assert!(!poly.is_empty(), "polygon must be non-empty");
+
+    let p = point.to_f64();
+    let n = poly.len();
+    let mut best = (poly[0].to_f64(), 0, f64::INFINITY);
+
+    for i in 0..n {
+        let closest = closest_point_on_segment(p, poly[i].to_f64(), poly[(i + 1) % n].to_f64());
+        let d = distance(p, closest);
+        if d < best.2 {
+            best = (closest, i, d);
+        }
+    }
+
+    best
+}
+
+/// Returns `true` if the axis-aligned bounding boxes of the line segments from `a0` to `a1`
+/// and from `b0` to `b1` overlap.
+///
+/// Bounding boxes which only touch are considered to overlap. Segments whose bounding boxes
+/// do not overlap cannot intersect, so this can be used to cheaply reject pairs of segments
+/// before performing a full intersection test. The comparison is exact, as it only compares
+/// coordinates.
+///
+/// # Examples
+/// ```
+/// use imageproc::geometry::segments_bbox_overlap;
+/// use imageproc::point::Point;
+///
+/// let (a0, a1) = (Point::new(0, 0), Point::new(4, 4));
+/// assert!(segments_bbox_overlap(a0, a1, Point::new(4, 0), Point::new(0, 4)));
+/// assert!(segments_bbox_overlap(a0, a1, Point::new(4, 4), Point::new(6, 9)));
+/// assert!(!segments_bbox_overlap(a0, a1, Point::new(5, 0), Point::new(6, 4)));
+/// ```
+pub fn segments_bbox_overlap<T>(a0: Point<T>, a1: Point<T>, b0: Point<T>, b1: Point<T>) -> bool
+where
+    T: PartialOrd + Copy,
+{
+    let ranges_overlap = |p: T, q: T, r: T, s: T| {
+        let (p, q) = if p <= q { (p, q) } else { (q, p) };
+        let (r, s) = if r <= s { (r, s) } else { (s, r) };
+        p <= s && r <= q
+    };
+    ranges_overlap(a0.x, a1.x, b0.x, b1.x) && ranges_overlap(a0.y, a1.y, b0.y, b1.y)
+}
+
+/// Returns the point on the line segment from `a` to `b` which is closest to `p`.
+fn closest_point_on_segment(p: Point<f64>, a: Point<f64>, b: Point<f64>) -> Point<f64> {
+    let ab = b - a;
+    let length_sq = ab.x * ab.x + ab.y * ab.y;
+    if length_sq == 0.0 {
+        return a;
+    }
+    let ap = p - a;
+    let t = ((ap.x * ab.x + ap.y * ab.y) / length_sq).clamp(0.0, 1.0);
+    a.lerp(b, t)
+}
+
+/// Finds the line which best fits a set of points, by minimising the sum of the squared
+/// perpendicular distances from the points to the line.
+///
+/// This is total least squares (orthogonal regression), rather than ordinary least squares,
+/// so lines of every orientation, including vertical lines, are handled equally well. The
+/// line passes through the mean of the points, in the direction in which they vary most.
+/// Its coefficients are normalized so that `a * a + b * b == 1`, so the residual of each
+/// point can be found using `Line::distance_from_point`. If the points vary equally in
+/// all directions, for example if there is only one point, then the line is horizontal.
+///
+/// # Panics
+///
+/// If `points` is empty.
+///
+/// # Examples
+/// ```
+/// use imageproc::geometry::fit_line;
+/// use imageproc::point::Point;
+///
+/// let points = [Point::new(3, 0), Point::new(3, 4), Point::new(3, 9)];
+/// let line = fit_line(&points);
+///
+/// assert!(line.distance_from_point(Point::new(3.0, 20.0)) < 1e-12);
+/// assert!((line.distance_from_point(Point::new(5.0, 1.0)) - 2.0).abs() < 1e-12);
+/// ```
+pub fn fit_line<T>(points: &[Point<T>]) -> Line
+where
+    T: NumCast + Copy,
+{
+    let mean = centroid(points);
+    let [[xx, xy], [_, yy]] = covariance(points);
+    let angle = 0.5 * (2.0 * xy).atan2(xx - yy);
+    let (sin, cos) = angle.sin_cos();
+
+    // The normal to the line is perpendicular to its direction (cos, sin).
+    Line {
+        a: -sin,
+        b: cos,
+        c: sin * mean.x - cos * mean.y,
+    }
+}
+
+/// Finds the circle which best fits a set of points, returning its centre and radius.
+///
+/// This uses the algebraic method of Kåsa, which minimises the sum over all points of
+/// `(d * d - r * r)^2`, where `d` is the distance from the point to the centre and `r` is
+/// the radius. This closely approximates the geometric best fit when the points are spread
+/// around much of the circle, but is biased towards smaller circles when they lie on a
+/// short arc.
+///
+/// Returns `None` if there are fewer than three points or if all points are collinear.
+///
+/// # Examples
+/// ```
+/// use imageproc::geometry::fit_circle;
+/// use imageproc::point::Point;
+///
+/// let points = [Point::new(5, 1), Point::new(1, 5), Point::new(-3, 1), Point::new(1, -3)];
+/// let (center, radius) = fit_circle(&points).unwrap();
+///
+/// assert!((center.x - 1.0).abs() < 1e-12 && (center.y - 1.0).abs() < 1e-12);
+/// assert!((radius - 4.0).abs() < 1e-12);
+///
+/// assert_eq!(fit_circle(&[Point::new(0, 0), Point::new(1, 1), Point::new(2, 2)]), None);
+/// ```
+pub fn fit_circle<T>(points: &[Point<T>]) -> Option<(Point<f64>, f64)>
+where
+    T: NumCast + Copy,
+{
+    if points.len() < 3 {
+        return None;
+    }
+
+    // Work relative to the mean of the points, for numerical stability.
+    let mean = centroid(points);
+    let (mut uu, mut uv, mut vv) = (0.0, 0.0, 0.0);
+    let (mut uuu, mut uvv, mut vvv, mut vuu) = (0.0, 0.0, 0.0, 0.0);
+    for p in points {
+        let d = p.to_f64() - mean;
+        let (u, v) = (d.x, d.y);
+        uu += u * u;
+        uv += u * v;
+        vv += v * v;
+        uuu += u * u * u;
+        uvv += u * v * v;
+        vvv += v * v * v;
+        vuu += v * u * u;
+    }
+
+    // Solve [uu uv; uv vv] * c = [uuu + uvv; vvv + vuu] / 2 for the centre c.
+    let det = uu * vv - uv * uv;
+    if det.abs() <= 1e-12 * (uu + vv) * (uu + vv) {
+        return None;
+    }
+    let (bu, bv) = ((uuu + uvv) / 2.0, (vvv + vuu) / 2.0);
+    let cu = (bu * vv - bv * uv) / det;
+    let cv = (bv * uu - bu * uv) / det;
+
+    let n = points.len() as f64;
+    let radius = (cu * cu + cv * cv + (uu + vv) / n).sqrt();
+    Some((Point::new(mean.x + cu, mean.y + cv), radius))
+}
+
+/// Finds the ellipse which best fits a set of points, using the direct least squares method
+/// of Fitzgibbon, Pilu and Fisher in the numerically stable form given by Halíř and Flusser.
+///
+/// The ellipse is returned as the [`RotatedRect`] which it is inscribed in, so `width` and
+/// `height` are the lengths of its major and minor axes, and `angle` is the angle of its
+/// major axis, in `(-π/2, π/2]`. This method always returns an ellipse if one exists, rather
+/// than another type of conic section, even when the points are better fit by, e.g., a hyperbola.
+///
+/// Returns `None` if there are fewer than five points, or if no ellipse fits the points,
+/// for example because they are collinear.
+///
+/// [`RotatedRect`]: ../rect/struct.RotatedRect.html
+pub fn fit_ellipse<T>(points: &[Point<T>]) -> Option<RotatedRect>
+where
+    T: NumCast + Copy,
+{
+    if points.len() < 5 {
+        return None;
+    }
+
+    // Normalize the points to have zero mean and unit root mean square distance from the
+    // origin, for numerical stability.
+    let mean = centroid(points);
+    let centered: Vec<Point<f64>> = points.iter().map(|p| p.to_f64() - mean).collect();
+    let n = points.len() as f64;
+    let scale = (centered.iter().map(|p| p.x * p.x + p.y * p.y).sum::<f64>() / n).sqrt();
+    if scale == 0.0 {
+        return None;
+    }
+
+    // Scatter matrices of the quadratic terms [x^2, xy, y^2] and linear terms [x, y, 1].
+    let (mut s1, mut s2, mut s3) = ([[0.0; 3]; 3], [[0.0; 3]; 3], [[0.0; 3]; 3]);
+    for p in &centered {
+        let (x, y) = (p.x / scale, p.y / scale);
+        let quadratic = [x * x, x * y, y * y];
+        let linear = [x, y, 1.0];
+        for i in 0..3 {
+            for j in 0..3 {
+                s1[i][j] += quadratic[i] * quadratic[j];
+                s2[i][j] += quadratic[i] * linear[j];
+                s3[i][j] += linear[i] * linear[j];
+            }
+        }
+    }
+
+    // The linear coefficients are given by t * the quadratic coefficients,
+    // where t = -s3^-1 * s2^T.
+    let s3_inv = invert_3x3(s3)?;
+    let mut t = [[0.0; 3]; 3];
+    for i in 0..3 {
+        for j in 0..3 {
+            t[i][j] = -(0..3).map(|k| s3_inv[i][k] * s2[j][k]).sum::<f64>();
+        }
+    }
+
+    // Reduced scatter matrix m = s1 + s2 * t, premultiplied by the inverse of the
+    // ellipse constraint matrix.
+    let mut m = s1;
+    for i in 0..3 {
+        for j in 0..3 {
+            m[i][j] += (0..3).map(|k| s2[i][k] * t[k][j]).sum::<f64>();
+        }
+    }
+    let m = [
+        [m[2][0] / 2.0, m[2][1] / 2.0, m[2][2] / 2.0],
+        [-m[1][0], -m[1][1], -m[1][2]],
+        [m[0][0] / 2.0, m[0][1] / 2.0, m[0][2] / 2.0],
+    ];
+
+    // The quadratic coefficients are the eigenvector of m satisfying the ellipse constraint.
+    let quadratic = real_eigenvalues_3x3(m)
+        .into_iter()
+        .filter_map(|lambda| eigenvector_3x3(m, lambda))
+        .find(|v| 4.0 * v[0] * v[2] - v[1] * v[1] > 0.0)?;
+    let linear: Vec<f64> = (0..3)
+        .map(|i| (0..3).map(|k| t[i][k] * quadratic[k]).sum())
+        .collect();
+
+    let (a, b, c) = (quadratic[0], quadratic[1], quadratic[2]);
+    let (d, e, f) = (linear[0], linear[1], linear[2]);
+
+    // Centre of the conic, and the conic's value there.
+    let denominator = b * b - 4.0 * a * c;
+    let x0 = (2.0 * c * d - b * e) / denominator;
+    let y0 = (2.0 * a * e - b * d) / denominator;
+    let f0 = f + (d * x0 + e * y0) / 2.0;
+
+    // Principal axes of the quadratic form. The eigenvector at this angle has the larger
+    // eigenvalue, and so lies along the minor axis.
+    let minor_angle = 0.5 * b.atan2(a - c);
+    let (sin, cos) = minor_angle.sin_cos();
+    let lambda_minor = a * cos * cos + b * sin * cos + c * sin * sin;
+    let lambda_major = a * sin * sin - b * sin * cos + c * cos * cos;
+    let (axis_major, axis_minor) = (-f0 / lambda_major, -f0 / lambda_minor);
+    if axis_major.is_nan() || axis_minor.is_nan() || axis_major <= 0.0 || axis_minor <= 0.0 {
+        return None;
+    }
+    let angle = if minor_angle > 0.0 {
+        minor_angle - PI / 2.0
+    } else {
+        minor_angle + PI / 2.0
+    };
+
+    Some(RotatedRect {
+        center: Point::new((mean.x + x0 * scale) as f32, (mean.y + y0 * scale) as f32),
+        width: (2.0 * axis_major.sqrt() * scale) as f32,
+        height: (2.0 * axis_minor.sqrt() * scale) as f32,
+        angle: angle as f32,
+    })
+}
+
+/// Returns the inverse of a 3x3 matrix, or `None` if it is singular.
+fn invert_3x3(m: [[f64; 3]; 3]) -> Option<[[f64; 3]; 3]> {
+    let cofactor = |i: usize, j: usize| {
+        let (r0, r1) = ((i + 1) % 3, (i + 2) % 3);
+        let (c0, c1) = ((j + 1) % 3, (j + 2) % 3);
+        m[r0][c0] * m[r1][c1] - m[r0][c1] * m[r1][c0]
+    };
+    let det = (0..3).map(|j| m[0][j] * cofactor(0, j)).sum::<f64>();
+    if det == 0.0 || !det.is_finite() {
+        return None;
+    }
+    let mut inverse = [[0.0; 3]; 3];
+    for i in 0..3 {
+        for j in 0..3 {
+            inverse[i][j] = cofactor(j, i) / det;
+        }
+    }
+    Some(inverse)
+}
+
+/// Returns the real eigenvalues of a 3x3 matrix, by solving its characteristic polynomial.
+fn real_eigenvalues_3x3(m: [[f64; 3]; 3]) -> Vec<f64> {
+    // The characteristic polynomial is x^3 + a * x^2 + b * x + c.
+    let trace = m[0][0] + m[1][1] + m[2][2];
+    let minors = m[0][0] * m[1][1] - m[0][1] * m[1][0] + m[0][0] * m[2][2] - m[0][2] * m[2][0]
+        + m[1][1] * m[2][2]
+        - m[1][2] * m[2][1];
+    let det = m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+        - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+        + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0]);
+    let (a, b, c) = (-trace, minors, -det);
+
+    // Substitute x = t - a / 3 to get t^3 + p * t + q.
+    let p = b - a * a / 3.0;
+    let q = 2.0 * a * a * a / 27.0 - a * b / 3.0 + c;
+    let shift = -a / 3.0;
+    let discriminant = q * q / 4.0 + p * p * p / 27.0;
+
+    if discriminant > 0.0 {
+        let root = discriminant.sqrt();
+        vec![(-q / 2.0 + root).cbrt() + (-q / 2.0 - root).cbrt() + shift]
+    } else if p == 0.0 {
+        vec![shift]
+    } else {
+        let r = 2.0 * (-p / 3.0).sqrt();
+        let phi = ((3.0 * q / (2.0 * p)) * (-3.0 / p).sqrt())
+            .clamp(-1.0, 1.0)
+            .acos()
+            / 3.0;
+        (0..3)
+            .map(|k| r * (phi - 2.0 * PI * k as f64 / 3.0).cos() + shift)
+            .collect()
+    }
+}
+
+/// Returns an eigenvector of a 3x3 matrix with the given eigenvalue, or `None` if
+/// the eigenspace of `lambda` is not one-dimensional.
+fn eigenvector_3x3(m: [[f64; 3]; 3], lambda: f64) -> Option<[f64; 3]> {
+    let mut rows = m;
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[i] -= lambda;
+    }
+    let cross = |u: [f64; 3], v: [f64; 3]| {
+        [
+            u[1] * v[2] - u[2] * v[1],
+            u[2] * v[0] - u[0] * v[2],
+            u[0] * v[1] - u[1] * v[0],
+        ]
+    };
+    let norm_sq = |v: &[f64; 3]| v[0] * v[0] + v[1] * v[1] + v[2] * v[2];
+    let best = [
+        cross(rows[0], rows[1]),
+        cross(rows[0], rows[2]),
+        cross(rows[1], rows[2]),
+    ]
+    .iter()
+    .copied()
+    .max_by(|u, v| {
+        norm_sq(u)
+            .partial_cmp(&norm_sq(v))
+            .unwrap_or(Ordering::Equal)
+    })?;
+    if norm_sq(&best) > 0.0 {
+        Some(best)
+    } else {
+        None
+    }
+}
+
+/// Rotates each point of each contour about `center`.
+///
+/// The output has the same structure as `contours`, with the points of each contour in
+/// the same order.
+pub fn rotate_contours(
+    contours: &[Vec<Point<f64>>],
+    center: Point<f64>,
+    rotation: Rotation,
+) -> Vec<Vec<Point<f64>>> {
+    contours
+        .iter()
+        .map(|contour| {
+            contour
+                .iter()
+                .map(|p| p.rotate_about(center, rotation))
+                .collect()
+        })
+        .collect()
+}
+
 /// Finds the rectangle of least area that includes all input points. This rectangle need not be axis-aligned.
 ///
 /// The returned points are the [top left, top right, bottom right, bottom left] points of this rectangle.
@@ -75,13 +877,205 @@ pub fn min_area_rect<T>(points: &[Point<T>]) -> [Point<T>; 4]
 where
     T: NumCast + Copy + Ord,
 {
-    let hull = convex_hull(&points);
+    min_area_rect_from_hull(&convex_hull(points))
+}
+
+/// Finds the rectangle of least area that includes all input points, as for [`min_area_rect`],
+/// and returns it as a [`RotatedRect`] with a normalized angle, together with the ratio of the
+/// area of the points' convex hull to the area of the rectangle.
+///
+/// This fill ratio lies in `[0, 1]` and measures how well the rectangle describes the points:
+/// it is 1 for points forming a rectangle, and around `π / 4` for points on a circle, whose
+/// minimal rectangle has an arbitrary orientation. If the rectangle has zero area, i.e. the
+/// points are collinear, then the fill ratio is 1.
+///
+/// The returned `angle` lies in `[0, π/2)`, i.e. in `[0°, 90°)`. It is measured from the image
+/// x-axis to the side of the rectangle whose direction lies in this range. As the image y-axis
+/// points downwards, positive angles appear as clockwise rotations. `width` is the length of
+/// this side and `height` is the length of the other, so `height` may exceed `width`.
+///
+/// For example, a rectangle whose long side is tilted by 30° has an angle of 30° and
+/// `width >= height`, whereas the same rectangle rotated by a further 90° also has an
+/// angle of 30°, but with `width <= height`.
+///
+/// The angle is not instead taken from the longer side. The longer side may point in any
+/// direction in `[0°, 180°)`, so an angle in `[0°, 90°)` together with `width >= height`
+/// could not describe every rectangle. The angle is in radians, as for every
+/// [`RotatedRect`], so the result can be passed directly to [`crop_rotated_rect`].
+///
+/// # Panics
+///
+/// If `points` is empty.
+///
+/// [`min_area_rect`]: fn.min_area_rect.html
+/// [`RotatedRect`]: ../rect/struct.RotatedRect.html
+/// [`crop_rotated_rect`]: ../geometric_transformations/fn.crop_rotated_rect.html
+pub fn min_area_rect_info<T>(points: &[Point<T>]) -> (RotatedRect, f64)
+where
+    T: NumCast + Copy + Ord,
+{
+    use std::f32::consts::{FRAC_PI_2, PI as PI_F32};
+
+    // min_area_rect rounds its corners outwards, which would perturb the angle.
+    let hull = convex_hull(points);
+    let corners = match hull.len() {
+        0 => panic!("no points are defined"),
+        1 | 2 => {
+            let c = min_area_rect_from_hull(&hull);
+            [c[0].to_f64(), c[1].to_f64(), c[2].to_f64(), c[3].to_f64()]
+        }
+        _ => rotating_calipers_f64(&hull),
+    };
+    let rect_area = signed_area(&corners).abs();
+    let fill_ratio = if rect_area > 0.0 {
+        (signed_area(&hull).abs() / rect_area).min(1.0)
+    } else {
+        1.0
+    };
+
+    let mut rect = RotatedRect::from_corners(&corners);
+    if rect.width == 0.0 && rect.height == 0.0 {
+        rect.angle = 0.0;
+        return (rect, fill_ratio);
+    }
+    if rect.width == 0.0 {
+        // The top edge is degenerate, so the orientation is given by the side edge.
+        rect.angle += FRAC_PI_2;
+        std::mem::swap(&mut rect.width, &mut rect.height);
+    }
+
+    rect.angle = rect.angle.rem_euclid(PI_F32);
+    while rect.angle >= FRAC_PI_2 {
+        rect.angle -= FRAC_PI_2;
+        std::mem::swap(&mut rect.width, &mut rect.height);
+    }
+    (rect, fill_ratio)
+}
+
+/// Finds a rectangle that includes all input points, with sides parallel to the principal
+/// axes of the points.
+///
+/// The rectangle's x-axis is the eigenvector of the [`covariance`] of the points with the
+/// largest eigenvalue, so `width` is the extent of the points along the direction in which
+/// they vary most. `angle` lies in `(-π/2, π/2]`. If the points vary equally in all directions
+/// then the rectangle is axis-aligned.
+///
+/// For elongated sets of points this orientation is often more intuitive than that of
+/// [`min_area_rect`], but the rectangle is not guaranteed to have minimum area.
+///
+/// # Panics
+///
+/// If `points` is empty.
+///
+/// [`covariance`]: ../point/fn.covariance.html
+/// [`min_area_rect`]: fn.min_area_rect.html
+pub fn pca_bounding_box<T>(points: &[Point<T>]) -> RotatedRect
+where
+    T: NumCast + Copy,
+{
+    let mean = centroid(points);
+    let [[xx, xy], [_, yy]] = covariance(points);
+    let angle = 0.5 * (2.0 * xy).atan2(xx - yy);
+    let (sin, cos) = angle.sin_cos();
+
+    let (mut u_min, mut u_max, mut v_min, mut v_max) = (f64::MAX, f64::MIN, f64::MAX, f64::MIN);
+    for p in points {
+        let d = p.to_f64() - mean;
+        let u = d.x * cos + d.y * sin;
+        let v = d.y * cos - d.x * sin;
+        u_min = u_min.min(u);
+        u_max = u_max.max(u);
+        v_min = v_min.min(v);
+        v_max = v_max.max(v);
+    }
+
+    let (u_mid, v_mid) = ((u_min + u_max) / 2.0, (v_min + v_max) / 2.0);
+    RotatedRect {
+        center: Point::new(
+            (mean.x + u_mid * cos - v_mid * sin) as f32,
+            (mean.y + u_mid * sin + v_mid * cos) as f32,
+        ),
+        width: (u_max - u_min) as f32,
+        height: (v_max - v_min) as f32,
+        angle: angle as f32,
+    }
+}
+
+/// Finds the rectangle of least area that includes all points of a convex polygon.
+/// This rectangle need not be axis-aligned.
+///
+/// This is equivalent to [`min_area_rect`], but avoids recomputing the convex hull when
+/// it is already known. `hull` must be a convex polygon with vertices listed in
+/// counter-clockwise order, as returned by [`convex_hull`]. If this is not the case
+/// then the result is unspecified.
+///
+/// The returned points are the [top left, top right, bottom right, bottom left] points of this rectangle.
+///
+/// # Panics
+///
+/// If `hull` is empty.
+///
+/// [`min_area_rect`]: fn.min_area_rect.html
+/// [`convex_hull`]: fn.convex_hull.html
+pub fn min_area_rect_from_hull<T>(hull: &[Point<T>]) -> [Point<T>; 4]
+where
+    T: NumCast + Copy,
+{
     match hull.len() {
         0 => panic!("no points are defined"),
         1 => [hull[0]; 4],
         2 => [hull[0], hull[1], hull[1], hull[0]],
-        _ => rotating_calipers(&hull),
+        _ => rotating_calipers(hull),
+    }
+}
+
+/// Finds the two points in a set that are farthest apart, and the distance between them.
+///
+/// This computes the convex hull of the input and then applies [rotating calipers]
+/// to the antipodal pairs of hull vertices, so runs in `O(n log n)` time.
+///
+/// # Panics
+///
+/// If `points` is empty.
+///
+/// [rotating calipers]: https://en.wikipedia.org/wiki/Rotating_calipers
+pub fn convex_hull_diameter<T>(points: &[Point<T>]) -> (Point<T>, Point<T>, f64)
+where
+    T: NumCast + Copy + Ord,
+{
+    let hull = convex_hull(points);
+    let n = hull.len();
+    match n {
+        0 => panic!("no points are defined"),
+        1 => return (hull[0], hull[0], 0.0),
+        2 => return (hull[0], hull[1], distance(hull[0], hull[1])),
+        _ => {}
+    }
+
+    let vertices: Vec<Point<f64>> = hull.iter().map(|p| p.to_f64()).collect();
+    // Twice the area of the triangle with vertices p, q, r.
+    let area = |p: Point<f64>, q: Point<f64>, r: Point<f64>| {
+        ((q.x - p.x) * (r.y - p.y) - (q.y - p.y) * (r.x - p.x)).abs()
+    };
+
+    let mut best = (0, 0, 0.0);
+    let mut j = 1;
+    for i in 0..n {
+        let next = (i + 1) % n;
+        while area(vertices[i], vertices[next], vertices[(j + 1) % n])
+            > area(vertices[i], vertices[next], vertices[j])
+        {
+            j = (j + 1) % n;
+        }
+        for &k in &[i, next] {
+            let d = distance(vertices[k], vertices[j]);
+            if d > best.2 {
+                best = (k, j, d);
+            }
+        }
     }
+
+    (hull[best.0], hull[best.1], best.2)
 }
 
 /// An implementation of [rotating calipers] used for determining the
@@ -89,6 +1083,21 @@ where
 ///
 /// [rotating calipers]: https://en.wikipedia.org/wiki/Rotating_calipers
 fn rotating_calipers<T>(points: &[Point<T>]) -> [Point<T>; 4]
+where
+    T: NumCast + Copy,
+{
+    let [p1, p2, p3, p4] = rotating_calipers_f64(points);
+    [
+        p1.floor_to(),
+        Point::new(p2.x.ceil(), p2.y.floor()).round_to(),
+        p3.ceil_to(),
+        Point::new(p4.x.floor(), p4.y.ceil()).round_to(),
+    ]
+}
+
+// Returns the corners of the minimum area rectangle found by rotating_calipers, in the
+// same order but without rounding.
+fn rotating_calipers_f64<T>(points: &[Point<T>]) -> [Point<f64>; 4]
 where
     T: NumCast + Copy,
 {
@@ -138,37 +1147,86 @@ where
     let i3 = if res[3].y > res[2].y { 3 } else { 2 };
     let i4 = if res[1].y > res[0].y { 1 } else { 0 };
 
-    [
-        Point::new(
-            cast(res[i1].x.floor()).unwrap(),
-            cast(res[i1].y.floor()).unwrap(),
-        ),
-        Point::new(
-            cast(res[i2].x.ceil()).unwrap(),
-            cast(res[i2].y.floor()).unwrap(),
-        ),
-        Point::new(
-            cast(res[i3].x.ceil()).unwrap(),
-            cast(res[i3].y.ceil()).unwrap(),
-        ),
-        Point::new(
-            cast(res[i4].x.floor()).unwrap(),
-            cast(res[i4].y.ceil()).unwrap(),
-        ),
-    ]
+    [res[i1], res[i2], res[i3], res[i4]]
+}
+
+/// The order in which the vertices of a polygon are listed.
+///
+/// Windings are defined with respect to a coordinate system whose y-axis points
+/// upwards. As the y-axis of an image points downwards, a `CounterClockwise` polygon
+/// appears clockwise when drawn onto an image.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Winding {
+    /// Vertices are listed in clockwise order.
+    Clockwise,
+    /// Vertices are listed in counter-clockwise order.
+    CounterClockwise,
 }
 
 /// Finds the convex hull of a set of points, using the [Graham scan algorithm].
 ///
+/// The vertices of the hull are listed in `CounterClockwise` order, starting from
+/// the point with the smallest y-coordinate (breaking ties by smallest x-coordinate).
+/// See [`convex_hull_with_winding`] to choose the winding.
+///
 /// [Graham scan algorithm]: https://en.wikipedia.org/wiki/Graham_scan
+/// [`convex_hull_with_winding`]: fn.convex_hull_with_winding.html
 pub fn convex_hull<T>(points_slice: &[Point<T>]) -> Vec<Point<T>>
 where
     T: NumCast + Copy + Ord,
 {
-    if points_slice.is_empty() {
-        return Vec::new();
+    convex_hull_with_winding(points_slice, Winding::CounterClockwise)
+}
+
+/// Finds the convex hull of a set of points, with vertices listed in the given `winding` order.
+///
+/// The first vertex is always the point with the smallest y-coordinate (breaking ties by
+/// smallest x-coordinate). Calling this function with `Winding::CounterClockwise` is
+/// equivalent to calling [`convex_hull`].
+///
+/// [`convex_hull`]: fn.convex_hull.html
+pub fn convex_hull_with_winding<T>(points_slice: &[Point<T>], winding: Winding) -> Vec<Point<T>>
+where
+    T: NumCast + Copy + Ord,
+{
+    let mut hull = points_slice.to_vec();
+    convex_hull_in_place(&mut hull);
+    if winding == Winding::Clockwise && hull.len() > 2 {
+        hull[1..].reverse();
+    }
+    hull
+}
+
+/// As [`convex_hull`], but reuses the allocation of `points`, replacing its contents
+/// with the vertices of their convex hull.
+///
+/// # Examples
+/// ```
+/// use imageproc::geometry::{convex_hull, convex_hull_in_place};
+/// use imageproc::point::Point;
+///
+/// let mut points = vec![
+///     Point::new(0, 0),
+///     Point::new(4, 4),
+///     Point::new(2, 1),
+///     Point::new(4, 0),
+///     Point::new(0, 4),
+/// ];
+/// let hull = convex_hull(&points);
+///
+/// convex_hull_in_place(&mut points);
+/// assert_eq!(points, hull);
+/// ```
+///
+/// [`convex_hull`]: fn.convex_hull.html
+pub fn convex_hull_in_place<T>(points: &mut Vec<Point<T>>)
+where
+    T: NumCast + Copy + Ord,
+{
+    if points.is_empty() {
+        return;
     }
-    let mut points: Vec<Point<T>> = points_slice.to_vec();
+
     let mut start_point_pos = 0;
     let mut start_point = points[0];
     for (i, &point) in points.iter().enumerate().skip(1) {
@@ -178,71 +1236,242 @@ where
         }
     }
     points.swap(0, start_point_pos);
-    points.remove(0);
-    points.sort_by(
-        |a, b| match orientation(start_point.to_i32(), a.to_i32(), b.to_i32()) {
-            Orientation::Collinear => {
-                if distance(start_point, *a) < distance(start_point, *b) {
-                    Ordering::Less
-                } else {
-                    Ordering::Greater
-                }
+    points[1..].sort_by(|a, b| match orientation(start_point, *a, *b) {
+        Orientation::Collinear => {
+            if distance(start_point, *a) < distance(start_point, *b) {
+                Ordering::Less
+            } else {
+                Ordering::Greater
             }
-            Orientation::Clockwise => Ordering::Greater,
-            Orientation::CounterClockwise => Ordering::Less,
-        },
-    );
-
-    let mut iter = points.iter().peekable();
-    let mut remaining_points = Vec::with_capacity(points.len());
-    while let Some(mut p) = iter.next() {
-        while iter.peek().is_some()
-            && orientation(
-                start_point.to_i32(),
-                p.to_i32(),
-                iter.peek().unwrap().to_i32(),
-            ) == Orientation::Collinear
-        {
-            p = iter.next().unwrap();
         }
-        remaining_points.push(p);
-    }
-
-    let mut stack: Vec<Point<T>> = vec![Point::new(
-        cast(start_point.x).unwrap(),
-        cast(start_point.y).unwrap(),
-    )];
+        Orientation::Clockwise => Ordering::Greater,
+        Orientation::CounterClockwise => Ordering::Less,
+    });
 
-    for p in points {
-        while stack.len() > 1
-            && orientation(
-                stack[stack.len() - 2].to_i32(),
-                stack[stack.len() - 1].to_i32(),
-                p.to_i32(),
-            ) != Orientation::CounterClockwise
+    // Graham scan, using the start of `points` as the stack. The stack never
+    // grows beyond the index of the next point to process.
+    let mut len = 1;
+    for i in 1..points.len() {
+        let p = points[i];
+        while len > 1
+            && orientation(points[len - 2], points[len - 1], p) != Orientation::CounterClockwise
         {
-            stack.pop();
+            len -= 1;
         }
-        stack.push(p);
+        points[len] = p;
+        len += 1;
     }
-    stack
+    points.truncate(len);
 }
 
+/// The direction of the turn made when travelling through three points in order.
+///
+/// As for [`Winding`], directions are defined with respect to a coordinate system whose
+/// y-axis points upwards, so appear reversed when drawn onto an image.
+///
+/// [`Winding`]: enum.Winding.html
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
-enum Orientation {
+pub enum Orientation {
+    /// The points lie on a single line.
     Collinear,
+    /// The points make a right turn.
     Clockwise,
+    /// The points make a left turn.
     CounterClockwise,
 }
 
-/// Determines whether p -> q -> r is a left turn, a right turn, or the points are collinear.
-fn orientation(p: Point<i32>, q: Point<i32>, r: Point<i32>) -> Orientation {
-    let val = (q.y - p.y) * (r.x - q.x) - (q.x - p.x) * (r.y - q.y);
-    match val.cmp(&0) {
-        Ordering::Equal => Orientation::Collinear,
-        Ordering::Greater => Orientation::Clockwise,
-        Ordering::Less => Orientation::CounterClockwise,
+/// Determines whether p -> q -> r is a left turn, a right turn, or the points are collinear.
+///
+/// Points with integer coordinates are handled exactly using `i64` arithmetic, so results
+/// are correct for coordinates far larger than any realistic image. Other coordinates are
+/// compared using `f64`s.
+///
+/// # Examples
+/// ```
+/// use imageproc::geometry::{orientation, Orientation};
+/// use imageproc::point::Point;
+///
+/// let (p, q) = (Point::new(0, 0), Point::new(2, 0));
+/// assert_eq!(orientation(p, q, Point::new(3, 1)), Orientation::CounterClockwise);
+/// assert_eq!(orientation(p, q, Point::new(3, -1)), Orientation::Clockwise);
+/// assert_eq!(orientation(p, q, Point::new(3, 0)), Orientation::Collinear);
+/// ```
+pub fn orientation<T>(p: Point<T>, q: Point<T>, r: Point<T>) -> Orientation
+where
+    T: NumCast + Copy,
+{
+    let val = match (to_exact_i64(p), to_exact_i64(q), to_exact_i64(r)) {
+        (Some(p), Some(q), Some(r)) => orientation_value_i64(p, q, r),
+        _ => None,
+    };
+    let ordering = match val {
+        Some(v) => v.cmp(&0),
+        None => {
+            let (p, q, r) = (p.to_f64(), q.to_f64(), r.to_f64());
+            let v = (q.y - p.y) * (r.x - q.x) - (q.x - p.x) * (r.y - q.y);
+            v.partial_cmp(&0.0).unwrap_or(Ordering::Equal)
+        }
+    };
+    match ordering {
+        Ordering::Greater => Orientation::Clockwise,
+        Ordering::Less => Orientation::CounterClockwise,
+        Ordering::Equal => Orientation::Collinear,
+    }
+}
+
+/// Converts a point to i64 coordinates, if this can be done without loss.
+fn to_exact_i64<T: NumCast + Copy>(p: Point<T>) -> Option<Point<i64>> {
+    let (x, y) = (p.x.to_i64()?, p.y.to_i64()?);
+    if x as f64 == p.x.to_f64()? && y as f64 == p.y.to_f64()? {
+        Some(Point::new(x, y))
+    } else {
+        None
+    }
+}
+
+/// The cross product used by `orientation`, or `None` if it overflows an i64.
+fn orientation_value_i64(p: Point<i64>, q: Point<i64>, r: Point<i64>) -> Option<i64> {
+    let lhs = (q.y.checked_sub(p.y)?).checked_mul(r.x.checked_sub(q.x)?)?;
+    let rhs = (q.x.checked_sub(p.x)?).checked_mul(r.y.checked_sub(q.y)?)?;
+    lhs.checked_sub(rhs)
+}
+
+/// Returns the offsets from its centre of the pixels in a Bresenham circle of the
+/// given radius, as computed by the [midpoint circle algorithm].
+///
+/// The offsets are listed without repetition in clockwise order as displayed in an image,
+/// i.e. with the y-axis pointing downwards, starting from `(0, -radius)`. For a radius of
+/// 3 these are the 16 pixels used by the FAST corner detector, in the order used by
+/// [`corners_fast9`]. A radius of 0 gives the single offset `(0, 0)`.
+///
+/// [midpoint circle algorithm]: https://en.wikipedia.org/wiki/Midpoint_circle_algorithm
+/// [`corners_fast9`]: ../corners/fn.corners_fast9.html
+pub fn bresenham_circle_points(radius: u32) -> Vec<(i32, i32)> {
+    // Offsets in the octant from (0, radius) to the diagonal, with x increasing.
+    let mut octant = Vec::new();
+    let (mut x, mut y) = (0i32, radius as i32);
+    let mut p = 1 - radius as i32;
+    while x <= y {
+        octant.push((x, y));
+        x += 1;
+        if p < 0 {
+            p += 2 * x + 1;
+        } else {
+            y -= 1;
+            p += 2 * (x - y) + 1;
+        }
+    }
+
+    // Each octant is traversed clockwise, so alternate octants are reversed.
+    let mut points = Vec::with_capacity(8 * octant.len());
+    points.extend(octant.iter().map(|&(x, y)| (x, -y)));
+    points.extend(octant.iter().rev().map(|&(x, y)| (y, -x)));
+    points.extend(octant.iter().map(|&(x, y)| (y, x)));
+    points.extend(octant.iter().rev().map(|&(x, y)| (x, y)));
+    points.extend(octant.iter().map(|&(x, y)| (-x, y)));
+    points.extend(octant.iter().rev().map(|&(x, y)| (-y, x)));
+    points.extend(octant.iter().map(|&(x, y)| (-y, -x)));
+    points.extend(octant.iter().rev().map(|&(x, y)| (-x, -y)));
+
+    points.dedup();
+    if points.len() > 1 && points.first() == points.last() {
+        points.pop();
+    }
+    points
+}
+
+/// Iterates over the coordinates in a line segment using
+/// [Bresenham's line drawing algorithm](https://en.wikipedia.org/wiki/Bresenham%27s_line_algorithm).
+pub struct BresenhamLineIter {
+    dx: f32,
+    dy: f32,
+    x: i32,
+    y: i32,
+    error: f32,
+    end_x: i32,
+    is_steep: bool,
+    y_step: i32,
+}
+
+impl BresenhamLineIter {
+    /// Creates a [`BresenhamLineIter`](struct.BresenhamLineIter.html) which will iterate over the integer coordinates
+    /// between `start` and `end`.
+    pub fn new(start: (f32, f32), end: (f32, f32)) -> BresenhamLineIter {
+        let (mut x0, mut y0) = (start.0, start.1);
+        let (mut x1, mut y1) = (end.0, end.1);
+
+        let is_steep = (y1 - y0).abs() > (x1 - x0).abs();
+        if is_steep {
+            swap(&mut x0, &mut y0);
+            swap(&mut x1, &mut y1);
+        }
+
+        if x0 > x1 {
+            swap(&mut x0, &mut x1);
+            swap(&mut y0, &mut y1);
+        }
+
+        let dx = x1 - x0;
+
+        BresenhamLineIter {
+            dx,
+            dy: (y1 - y0).abs(),
+            x: x0 as i32,
+            y: y0 as i32,
+            error: dx / 2f32,
+            end_x: x1 as i32,
+            is_steep,
+            y_step: if y0 < y1 { 1 } else { -1 },
+        }
+    }
+}
+
+impl Iterator for BresenhamLineIter {
+    type Item = (i32, i32);
+
+    fn next(&mut self) -> Option<(i32, i32)> {
+        if self.x > self.end_x {
+            None
+        } else {
+            let ret = if self.is_steep {
+                (self.y, self.x)
+            } else {
+                (self.x, self.y)
+            };
+
+            self.x += 1;
+            self.error -= self.dy;
+            if self.error < 0f32 {
+                self.y += self.y_step;
+                self.error += self.dx;
+            }
+
+            Some(ret)
+        }
+    }
+}
+
+/// Returns the pixels on the line segment between `start` and `end`, as computed by
+/// [Bresenham's line drawing algorithm].
+///
+/// These are exactly the pixels set by [`draw_line_segment_mut`] for the same endpoints,
+/// listed in order from `start` to `end`. Both endpoints are included.
+///
+/// [Bresenham's line drawing algorithm]: https://en.wikipedia.org/wiki/Bresenham%27s_line_algorithm
+/// [`draw_line_segment_mut`]: ../drawing/fn.draw_line_segment_mut.html
+pub fn line_points(start: Point<i32>, end: Point<i32>) -> Vec<Point<i32>> {
+    let mut points: Vec<Point<i32>> = BresenhamLineIter::new(
+        (start.x as f32, start.y as f32),
+        (end.x as f32, end.y as f32),
+    )
+    .map(|(x, y)| Point::new(x, y))
+    .collect();
+
+    // The iterator always steps in the direction of increasing major coordinate.
+    if points.first() != Some(&start) {
+        points.reverse();
     }
+    points
 }
 
 #[cfg(test)]
@@ -282,6 +1511,85 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_resample_contour() {
+        let line = [Point::new(0, 0), Point::new(4, 0), Point::new(4, 2)];
+        assert_eq!(
+            resample_contour(&line, 4, false),
+            [
+                Point::new(0.0, 0.0),
+                Point::new(2.0, 0.0),
+                Point::new(4.0, 0.0),
+                Point::new(4.0, 2.0)
+            ]
+        );
+
+        let square = [
+            Point::new(0, 0),
+            Point::new(2, 0),
+            Point::new(2, 2),
+            Point::new(0, 2),
+        ];
+        assert_eq!(
+            resample_contour(&square, 4, true),
+            [
+                Point::new(0.0, 0.0),
+                Point::new(2.0, 0.0),
+                Point::new(2.0, 2.0),
+                Point::new(0.0, 2.0)
+            ]
+        );
+        assert_eq!(resample_contour(&square, 8, true)[7], Point::new(0.0, 1.0));
+        assert!(resample_contour::<i32>(&[], 3, true).is_empty());
+    }
+
+    #[test]
+    fn test_offset_polygon() {
+        let square = [
+            Point::new(0, 0),
+            Point::new(2, 0),
+            Point::new(2, 2),
+            Point::new(0, 2),
+        ];
+        let inflated = [
+            Point::new(-1.0, -1.0),
+            Point::new(3.0, -1.0),
+            Point::new(3.0, 3.0),
+            Point::new(-1.0, 3.0),
+        ];
+        assert_eq!(offset_polygon(&square, 1.0), inflated);
+
+        let mut reversed = square.to_vec();
+        reversed.reverse();
+        let mut expected = inflated.to_vec();
+        expected.reverse();
+        assert_eq!(offset_polygon(&reversed, 1.0), expected);
+
+        assert_eq!(
+            offset_polygon(&square, -0.5),
+            [
+                Point::new(0.5, 0.5),
+                Point::new(1.5, 0.5),
+                Point::new(1.5, 1.5),
+                Point::new(0.5, 1.5)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_offset_polygon_bevels_sharp_corners() {
+        let spike = [Point::new(0, 0), Point::new(100, 5), Point::new(0, 10)];
+        let offset = offset_polygon(&spike, 1.0);
+        assert_eq!(offset.len(), 4);
+        for p in offset {
+            let nearest = spike
+                .iter()
+                .map(|q| distance(p, q.to_f64()))
+                .fold(f64::MAX, f64::min);
+            assert!(nearest <= OFFSET_MITER_LIMIT + 1e-9);
+        }
+    }
+
     #[test]
     fn convex_hull_points() {
         let star = vec![
@@ -309,6 +1617,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn convex_hull_with_winding_points() {
+        let star = vec![
+            Point::new(100, 20),
+            Point::new(90, 35),
+            Point::new(60, 25),
+            Point::new(90, 40),
+            Point::new(80, 55),
+            Point::new(101, 50),
+            Point::new(130, 60),
+            Point::new(115, 45),
+            Point::new(140, 30),
+            Point::new(120, 35),
+        ];
+        assert_eq!(
+            convex_hull_with_winding(&star, Winding::CounterClockwise),
+            convex_hull(&star)
+        );
+        assert_eq!(
+            convex_hull_with_winding(&star, Winding::Clockwise),
+            [
+                Point::new(100, 20),
+                Point::new(60, 25),
+                Point::new(80, 55),
+                Point::new(130, 60),
+                Point::new(140, 30)
+            ]
+        );
+    }
+
     #[test]
     fn convex_hull_points_empty_vec() {
         let points = convex_hull::<i32>(&vec![]);
@@ -342,6 +1680,568 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_orientation_large_coordinates() {
+        // Each product in the cross product is around 3.6e9, which overflows an i32.
+        let p = Point::new(-30000, -30000);
+        let q = Point::new(30000, -30000);
+        assert_eq!(
+            orientation(p, q, Point::new(30000, 30000)),
+            Orientation::CounterClockwise
+        );
+        assert_eq!(
+            orientation(p, q, Point::new(30000, -30001)),
+            Orientation::Clockwise
+        );
+        assert_eq!(
+            orientation(p, q, Point::new(-29999, -30000)),
+            Orientation::Collinear
+        );
+    }
+
+    #[test]
+    fn test_convex_hull_in_place() {
+        let points = vec![
+            Point::new(3, 1),
+            Point::new(0, 0),
+            Point::new(1, 1),
+            Point::new(2, 0),
+            Point::new(4, 0),
+            Point::new(2, 5),
+            Point::new(2, 2),
+        ];
+        let mut hull = points.clone();
+        convex_hull_in_place(&mut hull);
+        assert_eq!(
+            hull,
+            vec![Point::new(0, 0), Point::new(4, 0), Point::new(2, 5)]
+        );
+        assert_eq!(hull, convex_hull(&points));
+
+        let mut empty: Vec<Point<i32>> = vec![];
+        convex_hull_in_place(&mut empty);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_convex_hull_large_coordinates() {
+        let points = vec![
+            Point::new(-30000, -30000),
+            Point::new(30000, -30000),
+            Point::new(30000, 30000),
+            Point::new(-30000, 30000),
+            Point::new(0, 0),
+            Point::new(29999, 29000),
+            Point::new(-29000, 29999),
+        ];
+        assert_eq!(
+            convex_hull(&points),
+            vec![
+                Point::new(-30000, -30000),
+                Point::new(30000, -30000),
+                Point::new(30000, 30000),
+                Point::new(-30000, 30000),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_orientation_non_integer_coordinates() {
+        let p = Point::new(0.0, 0.0);
+        let q = Point::new(1.0, 0.0);
+        assert_eq!(
+            orientation(p, q, Point::new(2.0, 0.5)),
+            Orientation::CounterClockwise
+        );
+        assert_eq!(
+            orientation(p, q, Point::new(2.0, -0.5)),
+            Orientation::Clockwise
+        );
+    }
+
+    #[test]
+    fn test_polygon_centroid() {
+        // An L-shape made of a 2x2 square and a 2x1 rectangle, with unevenly
+        // spaced vertices.
+        let poly = vec![
+            Point::new(0, 0),
+            Point::new(4, 0),
+            Point::new(4, 1),
+            Point::new(2, 1),
+            Point::new(2, 2),
+            Point::new(1, 2),
+            Point::new(0, 2),
+        ];
+        let c = polygon_centroid(&poly);
+        // Areas 4 and 2 with centroids (1, 1) and (3, 0.5).
+        assert_approx_eq!(c.x, 10.0 / 6.0, 1e-12);
+        assert_approx_eq!(c.y, 5.0 / 6.0, 1e-12);
+
+        let mut clockwise = poly.clone();
+        clockwise.reverse();
+        let d = polygon_centroid(&clockwise);
+        assert_approx_eq!(d.x, c.x, 1e-12);
+        assert_approx_eq!(d.y, c.y, 1e-12);
+    }
+
+    #[test]
+    fn test_polygon_centroid_degenerate() {
+        let line = vec![Point::new(0, 0), Point::new(2, 2), Point::new(4, 4)];
+        assert_eq!(polygon_centroid(&line), Point::new(2.0, 2.0));
+        assert_eq!(polygon_centroid(&[Point::new(3, 5)]), Point::new(3.0, 5.0));
+    }
+
+    #[test]
+    fn test_approximate_polygon_dp_f64() {
+        let curve = vec![
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.1),
+            Point::new(2.0, -0.1),
+            Point::new(3.0, 2.5),
+            Point::new(4.0, 5.0),
+        ];
+        assert_eq!(
+            approximate_polygon_dp(&curve, 0.5, false),
+            vec![
+                Point::new(0.0, 0.0),
+                Point::new(2.0, -0.1),
+                Point::new(4.0, 5.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_simplify_to_area_budget() {
+        // Removing (4, 0) costs 2, then removing (8, 1) costs 28.
+        let poly = vec![
+            Point::new(0.0, 0.0),
+            Point::new(4.0, 0.0),
+            Point::new(8.0, 1.0),
+            Point::new(8.0, 8.0),
+            Point::new(0.0, 8.0),
+        ];
+        assert_eq!(simplify_to_area_budget(&poly, 0.0), poly);
+        assert_eq!(simplify_to_area_budget(&poly, 29.9).len(), 4);
+        assert_eq!(
+            simplify_to_area_budget(&poly, 30.0),
+            vec![
+                Point::new(0.0, 0.0),
+                Point::new(8.0, 8.0),
+                Point::new(0.0, 8.0)
+            ]
+        );
+        assert_eq!(simplify_to_area_budget(&poly, 1000.0).len(), 3);
+        assert_eq!(
+            simplify_to_area_budget(&poly[..2], 10.0),
+            poly[..2].to_vec()
+        );
+    }
+
+    #[test]
+    fn test_triangulate_polygon() {
+        // A concave polygon, listed in each direction.
+        let poly = vec![
+            Point::new(0, 0),
+            Point::new(4, 0),
+            Point::new(4, 4),
+            Point::new(2, 1),
+            Point::new(0, 4),
+        ];
+        let mut reversed = poly.clone();
+        reversed.reverse();
+
+        for p in &[poly, reversed] {
+            let triangles = triangulate_polygon(p);
+            assert_eq!(triangles.len(), 3);
+            let total: f64 = triangles.iter().map(|t| signed_area(t)).sum();
+            assert_eq!(total, signed_area(p));
+            for t in &triangles {
+                assert_eq!(signed_area(t).signum(), signed_area(p).signum());
+            }
+        }
+    }
+
+    #[test]
+    fn test_triangulate_polygon_degenerate() {
+        // Collinear vertices along an edge do not produce zero-area triangles.
+        let square = [
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 2.0),
+            Point::new(0.0, 2.0),
+        ];
+        let triangles = triangulate_polygon(&square);
+        assert!(triangles.len() <= 3);
+        assert!(triangles.iter().all(|t| signed_area(t) > 0.0));
+        let total: f64 = triangles.iter().map(|t| signed_area(t)).sum();
+        assert_eq!(total, 8.0);
+
+        // Repeated vertices are skipped.
+        let repeated = [
+            Point::new(0, 0),
+            Point::new(4, 0),
+            Point::new(4, 0),
+            Point::new(0, 4),
+            Point::new(0, 0),
+        ];
+        assert_eq!(
+            triangulate_polygon(&repeated),
+            vec![[Point::new(0, 0), Point::new(4, 0), Point::new(0, 4)]]
+        );
+
+        let line = [Point::new(0, 0), Point::new(1, 1), Point::new(2, 2)];
+        assert!(triangulate_polygon(&line).is_empty());
+        assert!(triangulate_polygon(&line[..2]).is_empty());
+    }
+
+    #[test]
+    fn test_fit_line() {
+        // Pairs of points either side of y = x / 2 + 1, at a perpendicular distance of 0.5.
+        let normal = Point::new(-1.0, 2.0);
+        let scale = 0.5 / 5f64.sqrt();
+        let points: Vec<Point<f64>> = (0..10)
+            .flat_map(|i| {
+                let p = Point::new(i as f64, i as f64 / 2.0 + 1.0);
+                let d = Point::new(normal.x * scale, normal.y * scale);
+                vec![p + d, p - d]
+            })
+            .collect();
+        let line = fit_line(&points);
+
+        assert_approx_eq!(line.a * line.a + line.b * line.b, 1.0, 1e-12);
+        for p in &[
+            Point::new(0.0, 1.0),
+            Point::new(4.0, 3.0),
+            Point::new(-6.0, -2.0),
+        ] {
+            assert!(line.distance_from_point(*p) < 1e-9);
+        }
+        for p in &points {
+            assert_approx_eq!(line.distance_from_point(*p), 0.5, 1e-9);
+        }
+
+        let single = fit_line(&[Point::new(2, 7)]);
+        assert_approx_eq!(
+            single.distance_from_point(Point::new(-5.0, 7.0)),
+            0.0,
+            1e-12
+        );
+    }
+
+    #[test]
+    fn test_fit_circle() {
+        // Integer points on the circle of radius 5 centred at (3, -2), unevenly spread.
+        let offsets = [
+            (5, 0),
+            (4, 3),
+            (3, 4),
+            (0, 5),
+            (-3, 4),
+            (-5, 0),
+            (0, -5),
+            (4, -3),
+        ];
+        let points: Vec<Point<i32>> = offsets
+            .iter()
+            .map(|&(dx, dy)| Point::new(3 + dx, -2 + dy))
+            .collect();
+
+        let (center, radius) = fit_circle(&points).unwrap();
+        assert_approx_eq!(center.x, 3.0, 1e-10);
+        assert_approx_eq!(center.y, -2.0, 1e-10);
+        assert_approx_eq!(radius, 5.0, 1e-10);
+
+        assert_eq!(fit_circle(&points[..2]), None);
+        let vertical = [Point::new(1, 0), Point::new(1, 5), Point::new(1, 9)];
+        assert_eq!(fit_circle(&vertical), None);
+        assert_eq!(fit_circle(&[Point::new(4, 4); 5]), None);
+    }
+
+    #[test]
+    fn test_fit_ellipse() {
+        // Points on an ellipse with semi-axes 6 and 2, rotated so that its major axis
+        // is at 30 degrees to the x-axis, centred at (10, 5).
+        let (sin, cos) = (PI / 6.0).sin_cos();
+        let points: Vec<Point<f64>> = (0..12)
+            .map(|i| {
+                let t = i as f64 * 0.5;
+                let (x, y) = (6.0 * t.cos(), 2.0 * t.sin());
+                Point::new(10.0 + x * cos - y * sin, 5.0 + x * sin + y * cos)
+            })
+            .collect();
+
+        let ellipse = fit_ellipse(&points).unwrap();
+        assert!((ellipse.center.x - 10.0).abs() < 1e-4);
+        assert!((ellipse.center.y - 5.0).abs() < 1e-4);
+        assert!((ellipse.width - 12.0).abs() < 1e-4);
+        assert!((ellipse.height - 4.0).abs() < 1e-4);
+        assert!((ellipse.angle - std::f32::consts::FRAC_PI_6).abs() < 1e-4);
+
+        assert_eq!(fit_ellipse(&points[..4]), None);
+        let line: Vec<Point<i32>> = (0..10).map(|i| Point::new(i, 2 * i + 1)).collect();
+        assert_eq!(fit_ellipse(&line), None);
+    }
+
+    #[test]
+    fn test_closest_point_on_polygon() {
+        let triangle = [Point::new(0, 0), Point::new(6, 0), Point::new(0, 6)];
+
+        // Projects onto the interior of the hypotenuse.
+        let (closest, edge, d) = closest_point_on_polygon(Point::new(4, 4), &triangle);
+        assert_eq!(closest, Point::new(3.0, 3.0));
+        assert_eq!(edge, 1);
+        assert_approx_eq!(d, 2f64.sqrt(), 1e-12);
+
+        // Clamps to a vertex.
+        let (closest, edge, d) = closest_point_on_polygon(Point::new(-3, -4), &triangle);
+        assert_eq!(closest, Point::new(0.0, 0.0));
+        assert_eq!(edge, 0);
+        assert_eq!(d, 5.0);
+
+        // Points inside the polygon project onto the boundary.
+        let (closest, edge, d) = closest_point_on_polygon(Point::new(1, 2), &triangle);
+        assert_eq!(closest, Point::new(0.0, 2.0));
+        assert_eq!(edge, 2);
+        assert_eq!(d, 1.0);
+
+        let (closest, edge, d) = closest_point_on_polygon(Point::new(1, 1), &[Point::new(4, 5)]);
+        assert_eq!((closest, edge, d), (Point::new(4.0, 5.0), 0, 5.0));
+    }
+
+    #[test]
+    fn test_segments_bbox_overlap() {
+        let (a0, a1) = (Point::new(1.0, 5.0), Point::new(3.0, 1.0));
+
+        // Endpoint order does not matter.
+        assert!(segments_bbox_overlap(
+            a0,
+            a1,
+            Point::new(2.0, 0.0),
+            Point::new(2.5, 2.0)
+        ));
+        assert!(segments_bbox_overlap(
+            a1,
+            a0,
+            Point::new(2.5, 2.0),
+            Point::new(2.0, 0.0)
+        ));
+
+        // Overlapping x-ranges but disjoint y-ranges.
+        assert!(!segments_bbox_overlap(
+            a0,
+            a1,
+            Point::new(0.0, 6.0),
+            Point::new(4.0, 7.0)
+        ));
+
+        // A degenerate segment touching the corner of the bounding box, and one just outside it.
+        let p = Point::new(3.0, 5.0);
+        assert!(segments_bbox_overlap(a0, a1, p, p));
+        let q = Point::new(3.0 + 1e-9, 5.0);
+        assert!(!segments_bbox_overlap(a0, a1, q, q));
+    }
+
+    #[test]
+    fn test_centroid_distance_signature() {
+        let square = vec![
+            Point::new(1.0, 1.0),
+            Point::new(3.0, 1.0),
+            Point::new(3.0, 2.0),
+            Point::new(3.0, 3.0),
+            Point::new(1.0, 3.0),
+        ];
+        let signature = centroid_distance_signature(&square);
+        let expected = [2f64.sqrt(), 2f64.sqrt(), 1.0, 2f64.sqrt(), 2f64.sqrt()];
+        assert_eq!(signature.len(), expected.len());
+        for (s, e) in signature.iter().zip(expected.iter()) {
+            assert_approx_eq!(s, e, 1e-12);
+        }
+
+        assert!(centroid_distance_signature::<i32>(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_bresenham_circle_points_radius_three() {
+        // See Note [FAST circle labels] in corners.rs.
+        let expected = vec![
+            (0, -3),
+            (1, -3),
+            (2, -2),
+            (3, -1),
+            (3, 0),
+            (3, 1),
+            (2, 2),
+            (1, 3),
+            (0, 3),
+            (-1, 3),
+            (-2, 2),
+            (-3, 1),
+            (-3, 0),
+            (-3, -1),
+            (-2, -2),
+            (-1, -3),
+        ];
+        assert_eq!(bresenham_circle_points(3), expected);
+    }
+
+    #[test]
+    fn test_bresenham_circle_points_matches_draw_hollow_circle() {
+        use crate::drawing::draw_hollow_circle_mut;
+        use image::{GrayImage, Luma};
+
+        assert_eq!(bresenham_circle_points(0), vec![(0, 0)]);
+
+        for radius in 1..12 {
+            let points = bresenham_circle_points(radius);
+
+            let mut unique = points.clone();
+            unique.sort();
+            unique.dedup();
+            assert_eq!(unique.len(), points.len());
+
+            let size = 2 * radius + 1;
+            let mut drawn = GrayImage::new(size, size);
+            let c = radius as i32;
+            draw_hollow_circle_mut(&mut drawn, (c, c), c, Luma([255u8]));
+
+            let mut expected = GrayImage::new(size, size);
+            for (dx, dy) in points {
+                expected.put_pixel((c + dx) as u32, (c + dy) as u32, Luma([255u8]));
+            }
+            assert_pixels_eq!(drawn, expected);
+        }
+    }
+
+    #[test]
+    fn test_line_points() {
+        let expected = vec![
+            Point::new(4, 2),
+            Point::new(3, 1),
+            Point::new(2, 1),
+            Point::new(1, 0),
+            Point::new(0, 0),
+        ];
+        assert_eq!(line_points(Point::new(4, 2), Point::new(0, 0)), expected);
+        assert_eq!(
+            line_points(Point::new(2, 3), Point::new(2, 3)),
+            vec![Point::new(2, 3)]
+        );
+    }
+
+    #[test]
+    fn test_line_points_matches_draw_line_segment() {
+        use crate::drawing::draw_line_segment_mut;
+        use image::{GrayImage, Luma};
+
+        let endpoints = [
+            ((1, 1), (8, 3)),
+            ((8, 3), (1, 1)),
+            ((2, 9), (4, 0)),
+            ((0, 5), (9, 5)),
+            ((7, 7), (0, 0)),
+            ((3, 0), (5, 9)),
+        ];
+
+        for &((x0, y0), (x1, y1)) in &endpoints {
+            let (start, end) = (Point::new(x0, y0), Point::new(x1, y1));
+            let points = line_points(start, end);
+            assert_eq!(points.first(), Some(&start));
+            assert_eq!(points.last(), Some(&end));
+
+            let mut drawn = GrayImage::new(10, 10);
+            draw_line_segment_mut(
+                &mut drawn,
+                (x0 as f32, y0 as f32),
+                (x1 as f32, y1 as f32),
+                Luma([255u8]),
+            );
+
+            let mut expected = GrayImage::new(10, 10);
+            for p in points {
+                expected.put_pixel(p.x as u32, p.y as u32, Luma([255u8]));
+            }
+            assert_pixels_eq!(drawn, expected);
+        }
+    }
+
+    #[test]
+    fn test_rotate_contours() {
+        let contours = vec![
+            vec![Point::new(2.0, 1.0), Point::new(1.0, 2.0)],
+            vec![],
+            vec![Point::new(1.0, 1.0)],
+        ];
+        let rotated = rotate_contours(&contours, Point::new(1.0, 1.0), Rotation::new(PI / 2.0));
+
+        let expected = vec![
+            vec![Point::new(1.0, 0.0), Point::new(2.0, 1.0)],
+            vec![],
+            vec![Point::new(1.0, 1.0)],
+        ];
+        assert_eq!(rotated.len(), expected.len());
+        for (r, e) in rotated.iter().zip(expected.iter()) {
+            assert_eq!(r.len(), e.len());
+            for (p, q) in r.iter().zip(e.iter()) {
+                assert_approx_eq!(p.x, q.x, 1e-12);
+                assert_approx_eq!(p.y, q.y, 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_convex_hull_diameter() {
+        let star = vec![
+            Point::new(100, 20),
+            Point::new(90, 35),
+            Point::new(60, 25),
+            Point::new(90, 40),
+            Point::new(80, 55),
+            Point::new(101, 50),
+            Point::new(130, 60),
+            Point::new(115, 45),
+            Point::new(140, 30),
+            Point::new(120, 35),
+        ];
+
+        let mut expected = 0.0;
+        for p in &star {
+            for q in &star {
+                expected = f64::max(expected, distance(*p, *q));
+            }
+        }
+
+        let (p, q, d) = convex_hull_diameter(&star);
+        assert_eq!(d, expected);
+        assert_eq!(distance(p, q), expected);
+
+        let single = [Point::new(3, 4)];
+        assert_eq!(
+            convex_hull_diameter(&single),
+            (Point::new(3, 4), Point::new(3, 4), 0.0)
+        );
+    }
+
+    #[test]
+    fn test_min_area_rect_from_hull() {
+        let points = [
+            Point::new(100, 20),
+            Point::new(140, 30),
+            Point::new(130, 60),
+            Point::new(80, 55),
+            Point::new(60, 25),
+            Point::new(100, 40),
+        ];
+        assert_eq!(
+            min_area_rect_from_hull(&convex_hull(&points)),
+            min_area_rect(&points)
+        );
+        assert_eq!(
+            min_area_rect_from_hull(&[Point::new(3, 4)]),
+            [Point::new(3, 4); 4]
+        );
+    }
+
     #[test]
     fn test_min_area() {
         assert_eq!(
@@ -360,4 +2260,96 @@ mod tests {
             ]
         )
     }
+
+    #[test]
+    fn test_pca_bounding_box() {
+        let grid: Vec<Point<f64>> = (0..=10)
+            .flat_map(|x| (0..=2).map(move |y| Point::new(x as f64, y as f64)))
+            .collect();
+
+        let axis_aligned = pca_bounding_box(&grid);
+        assert_eq!(axis_aligned.angle, 0.0);
+        assert_eq!((axis_aligned.width, axis_aligned.height), (10.0, 2.0));
+        assert_eq!(axis_aligned.center, Point::new(5.0, 1.0));
+
+        // Rotating by -30 degrees appears as a clockwise rotation by 30 degrees.
+        let rotation = Rotation::new(-PI / 6.0);
+        let center = Point::new(20.0, 30.0);
+        let tilted: Vec<Point<f64>> = grid.iter().map(|p| p.rotate(rotation) + center).collect();
+
+        let rect = pca_bounding_box(&tilted);
+        assert!((rect.angle - std::f32::consts::FRAC_PI_6).abs() < 1e-5);
+        assert!((rect.width - 10.0).abs() < 1e-4);
+        assert!((rect.height - 2.0).abs() < 1e-4);
+        let expected_center = Point::new(5.0, 1.0).rotate(rotation) + center;
+        assert!((rect.center.x as f64 - expected_center.x).abs() < 1e-4);
+        assert!((rect.center.y as f64 - expected_center.y).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_min_area_rect_info_tilted_by_30_degrees() {
+        // A rectangle with its long side along (52, 30), i.e. at atan(30 / 52) ~ 30 degrees,
+        // and its short side along (-15, 26). The long side is sqrt(3604) ~ 60.03 pixels and the
+        // short side is sqrt(901) ~ 30.02 pixels.
+        let tilted = [
+            Point::new(20, 0),
+            Point::new(72, 30),
+            Point::new(57, 56),
+            Point::new(5, 26),
+        ];
+        let expected_angle = 30f32.atan2(52.0);
+        let (long, short) = (3604f32.sqrt(), 901f32.sqrt());
+
+        let (info, fill_ratio) = min_area_rect_info(&tilted);
+        assert!((fill_ratio - 1.0).abs() < 1e-9);
+        assert!((info.center.x - 38.5).abs() < 1e-4);
+        assert!((info.center.y - 28.0).abs() < 1e-4);
+        assert!((info.angle - expected_angle).abs() < 1e-4);
+        assert!((info.angle.to_degrees() - 30.0).abs() < 0.1);
+        assert!((info.width - long).abs() < 1e-3);
+        assert!((info.height - short).abs() < 1e-3);
+
+        // Rotating by 90 degrees about the origin puts the long side at ~120 degrees, outside
+        // [0, 90), so the angle is taken from the short side, which is now at ~30 degrees.
+        let rotated: Vec<Point<i32>> = tilted.iter().map(|p| Point::new(-p.y, p.x)).collect();
+        let (info, fill_ratio) = min_area_rect_info(&rotated);
+        assert!((fill_ratio - 1.0).abs() < 1e-9);
+        assert!((info.angle - expected_angle).abs() < 1e-4);
+        assert!((info.angle.to_degrees() - 30.0).abs() < 0.1);
+        assert!((info.width - short).abs() < 1e-3);
+        assert!((info.height - long).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_min_area_rect_info() {
+        let tall = [
+            Point::new(0, 0),
+            Point::new(2, 0),
+            Point::new(2, 5),
+            Point::new(0, 5),
+        ];
+        let (info, fill_ratio) = min_area_rect_info(&tall);
+        assert_eq!(fill_ratio, 1.0);
+        assert_eq!(info.angle, 0.0);
+        assert_eq!((info.width, info.height), (2.0, 5.0));
+
+        let vertical_segment = [Point::new(1, 1), Point::new(1, 4)];
+        let (info, fill_ratio) = min_area_rect_info(&vertical_segment);
+        assert_eq!(fill_ratio, 1.0);
+        assert_eq!(info.angle, 0.0);
+        assert_eq!((info.width, info.height), (0.0, 3.0));
+
+        // A circle is poorly described by a rectangle.
+        let circle: Vec<Point<i32>> = (0..64)
+            .map(|i| {
+                let t = i as f64 * 2.0 * PI / 64.0;
+                Point::new(
+                    (100.0 * t.cos()).round() as i32,
+                    (100.0 * t.sin()).round() as i32,
+                )
+            })
+            .collect();
+        let (_, fill_ratio) = min_area_rect_info(&circle);
+        assert!((fill_ratio - PI / 4.0).abs() < 0.01);
+    }
 }
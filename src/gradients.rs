@@ -101,6 +101,45 @@ pub fn sobel_gradients(image: &GrayImage) -> Image<Luma<u16>> {
     gradients(image, &HORIZONTAL_SOBEL, &VERTICAL_SOBEL, |p| p)
 }
 
+/// Returns the horizontal and vertical gradients of an image, computed
+/// using the [`HORIZONTAL_SOBEL`](static.HORIZONTAL_SOBEL.html) and
+/// [`VERTICAL_SOBEL`](static.VERTICAL_SOBEL.html) kernels respectively.
+pub fn gradients_xy(image: &GrayImage) -> (Image<Luma<i16>>, Image<Luma<i16>>) {
+    (horizontal_sobel(image), vertical_sobel(image))
+}
+
+/// Returns the magnitude and orientation of the gradient at each pixel of an image,
+/// computed using Sobel filters.
+///
+/// Orientations are given in radians, in the range `[-pi, pi]`, and are measured
+/// from the positive x-axis towards the positive y-axis (i.e. downwards in the image).
+/// Pixels with zero gradient have an orientation of zero.
+///
+/// Magnitudes are in the units of the Sobel filter responses, which are 8 times the
+/// intensity change per pixel for a linear ramp. At the image border, pixels outside the
+/// image are taken to have the value of the nearest pixel inside it, so gradients
+/// perpendicular to the border are underestimated there.
+pub fn gradient_magnitude_orientation(image: &GrayImage) -> (Image<Luma<f32>>, Image<Luma<f32>>) {
+    let (gx, gy) = gradients_xy(image);
+    let (width, height) = image.dimensions();
+
+    let mut magnitudes = Image::new(width, height);
+    let mut orientations = Image::new(width, height);
+
+    for (h, v, m, o) in multizip((
+        gx.iter(),
+        gy.iter(),
+        magnitudes.iter_mut(),
+        orientations.iter_mut(),
+    )) {
+        let (h, v) = (*h as f32, *v as f32);
+        *m = (h.powi(2) + v.powi(2)).sqrt();
+        *o = v.atan2(h);
+    }
+
+    (magnitudes, orientations)
+}
+
 /// Computes per-channel gradients using Sobel filters and calls `f`
 /// to compute each output pixel.
 ///
@@ -364,6 +403,46 @@ mod tests {
         assert_pixels_eq!(filtered, expected);
     }
 
+    #[test]
+    fn test_gradient_magnitude_orientation() {
+        let image = gray_image!(
+            3, 2, 1;
+            6, 5, 4;
+            9, 8, 7);
+
+        let (gx, gy) = gradients_xy(&image);
+        assert_pixels_eq!(gx, horizontal_sobel(&image));
+        assert_pixels_eq!(gy, vertical_sobel(&image));
+
+        let (magnitudes, orientations) = gradient_magnitude_orientation(&image);
+        for (x, y, m) in magnitudes.enumerate_pixels() {
+            let h = gx.get_pixel(x, y)[0] as f32;
+            let v = gy.get_pixel(x, y)[0] as f32;
+            assert_approx_eq!(m[0], (h * h + v * v).sqrt());
+            assert_approx_eq!(orientations.get_pixel(x, y)[0], v.atan2(h));
+        }
+
+        let center = orientations.get_pixel(1, 1)[0];
+        assert!(center >= -std::f32::consts::PI && center <= std::f32::consts::PI);
+    }
+
+    #[test]
+    fn test_gradient_magnitude_orientation_border() {
+        let image = gray_image!(
+            0, 10, 20, 30;
+            0, 10, 20, 30;
+            0, 10, 20, 30);
+
+        let (magnitudes, orientations) = gradient_magnitude_orientation(&image);
+        // Replicating the border pixels halves the gradient at the left and right edges.
+        for y in 0..3 {
+            assert_eq!(magnitudes.get_pixel(0, y)[0], 40.0);
+            assert_eq!(magnitudes.get_pixel(1, y)[0], 80.0);
+            assert_eq!(magnitudes.get_pixel(3, y)[0], 40.0);
+            assert_eq!(orientations.get_pixel(1, y)[0], 0.0);
+        }
+    }
+
     #[bench]
     fn bench_sobel_gradients(b: &mut Bencher) {
         let image = gray_bench_image(500, 500);
@@ -1,8 +1,16 @@
 //! Functions for mapping over pixels, colors or subpixels of images.
 
-use image::{Bgr, Bgra, GenericImage, ImageBuffer, Luma, LumaA, Pixel, Primitive, Rgb, Rgba};
+use image::{
+    Bgr, Bgra, GenericImage, GenericImageView, ImageBuffer, Luma, LumaA, Pixel, Primitive, Rgb,
+    Rgba,
+};
 
-use crate::definitions::Image;
+use crate::definitions::{Clamp, Image};
+use crate::math::cast;
+use conv::ValueInto;
+use num::traits::WrappingSub;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 
 /// The type obtained by replacing the channel type of a given `Pixel` type.
 /// The output type must have the same name of channels as the input type, or
@@ -113,6 +121,68 @@ where
     out
 }
 
+/// Replaces each subpixel `c` of the input image with `lut[c]`.
+///
+/// # Examples
+/// ```
+/// # extern crate image;
+/// # #[macro_use]
+/// # extern crate imageproc;
+/// # fn main() {
+/// use imageproc::map::apply_lut;
+///
+/// let image = gray_image!(
+///     1, 2;
+///     3, 4);
+///
+/// let mut lut = [0u8; 256];
+/// for (i, v) in lut.iter_mut().enumerate() {
+///     *v = 255 - i as u8;
+/// }
+///
+/// let inverted = gray_image!(
+///     254, 253;
+///     252, 251);
+///
+/// assert_pixels_eq!(apply_lut(&image, &lut), inverted);
+/// # }
+/// ```
+pub fn apply_lut<I, P>(image: &I, lut: &[u8; 256]) -> Image<ChannelMap<P, u8>>
+where
+    I: GenericImageView<Pixel = P>,
+    P: WithChannel<u8> + Pixel<Subpixel = u8> + 'static,
+{
+    let (width, height) = image.dimensions();
+    let mut out: Image<ChannelMap<P, u8>> = ImageBuffer::new(width, height);
+
+    for y in 0..height {
+        for x in 0..width {
+            let pixel = unsafe { image.unsafe_get_pixel(x, y) };
+            let out_channels = out.get_pixel_mut(x, y).channels_mut();
+            for (o, &c) in out_channels.iter_mut().zip(pixel.channels()) {
+                *o = lut[c as usize];
+            }
+        }
+    }
+
+    out
+}
+
+/// Replaces each subpixel `c` of the input image with `lut[c]`, in place.
+///
+/// See [`apply_lut`](fn.apply_lut.html).
+pub fn apply_lut_mut<P>(image: &mut Image<P>, lut: &[u8; 256])
+where
+    P: Pixel<Subpixel = u8> + 'static,
+{
+    #[cfg(feature = "rayon")]
+    let iter = image.par_iter_mut();
+    #[cfg(not(feature = "rayon"))]
+    let iter = image.iter_mut();
+
+    iter.for_each(|c| *c = lut[*c as usize]);
+}
+
 /// Applies `f` to the color of each pixel in the input image.
 ///
 /// # Examples
@@ -159,6 +229,103 @@ where
     out
 }
 
+/// Converts an image to grayscale by applying `f` to the channels of each pixel.
+///
+/// This generalises a fixed grayscale conversion, for example to allow custom channel
+/// weights. `f` receives a slice containing every channel of a pixel, in the order
+/// used by the pixel type.
+///
+/// # Examples
+/// ```
+/// # extern crate image;
+/// # #[macro_use]
+/// # extern crate imageproc;
+/// # fn main() {
+/// use imageproc::map::to_luma_with;
+///
+/// let image = rgb_image!(
+///     [100, 50, 10], [0, 200, 0];
+///     [30, 30, 30], [255, 0, 255]);
+///
+/// // Ignore the blue channel.
+/// let gray = to_luma_with(&image, |c| (c[0] as f32 * 0.5 + c[1] as f32 * 0.5) as u8);
+///
+/// let expected = gray_image!(
+///     75, 100;
+///     30, 127);
+///
+/// assert_pixels_eq!(gray, expected);
+/// # }
+/// ```
+pub fn to_luma_with<I, C, F>(image: &I, f: F) -> Image<Luma<C>>
+where
+    I: GenericImage,
+    C: Primitive + 'static,
+    F: Fn(&[<I::Pixel as Pixel>::Subpixel]) -> C,
+{
+    map_colors(image, |p| Luma([f(p.channels())]))
+}
+
+/// Weights for converting RGB to luma as specified by ITU-R BT.601.
+pub const REC601: [f64; 3] = [0.299, 0.587, 0.114];
+
+/// Weights for converting RGB to luma as specified by ITU-R BT.709.
+/// These are the weights used by `image`'s own grayscale conversions.
+pub const REC709: [f64; 3] = [0.2126, 0.7152, 0.0722];
+
+/// Converts an RGB image to grayscale by taking the weighted sum of the red, green and blue
+/// channels of each pixel, with weights given in that order.
+///
+/// The weighted sum is clamped to the range of `C`, after rounding to the nearest integer
+/// if `C` is an integer type. Weights for common standards are provided by [`REC601`] and
+/// [`REC709`], but any weights can be used.
+///
+/// [`REC601`]: constant.REC601.html
+/// [`REC709`]: constant.REC709.html
+///
+/// # Examples
+/// ```
+/// # extern crate image;
+/// # #[macro_use]
+/// # extern crate imageproc;
+/// # fn main() {
+/// use imageproc::map::{to_luma_weighted, REC601};
+///
+/// let image = rgb_image!(
+///     [100, 100, 100], [255, 0, 0];
+///     [0, 0, 255], [200, 220, 255]);
+///
+/// let expected = gray_image!(
+///     100, 76;
+///      29, 218);
+///
+/// assert_pixels_eq!(to_luma_weighted(&image, REC601), expected);
+///
+/// // Custom weights may sum to more than 1, in which case large values saturate.
+/// let expected = gray_image!(
+///     200, 0;
+///     255, 255);
+///
+/// assert_pixels_eq!(to_luma_weighted(&image, [-1.0, 0.0, 3.0]), expected);
+/// # }
+/// ```
+pub fn to_luma_weighted<I, C>(image: &I, weights: [f64; 3]) -> Image<Luma<C>>
+where
+    I: GenericImage<Pixel = Rgb<C>>,
+    C: Primitive + ValueInto<f64> + Clamp<f64> + 'static,
+{
+    // Casting 0.5 to an integer type truncates it to 0.
+    let round = <C as num::NumCast>::from(0.5) == Some(C::zero());
+    map_colors(image, |p| {
+        let sum = weights
+            .iter()
+            .zip(p.0.iter())
+            .map(|(w, &c)| w * cast::<C, f64>(c))
+            .sum::<f64>();
+        Luma([C::clamp(if round { sum.round() } else { sum })])
+    })
+}
+
 /// Applies `f` to the colors of the pixels in the input images.
 ///
 /// Requires `image1` and `image2` to have the same dimensions.
@@ -219,6 +386,65 @@ where
     out
 }
 
+/// How to handle negative channel differences in [`difference_image`].
+///
+/// [`difference_image`]: fn.difference_image.html
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DiffMode {
+    /// Each output channel is the absolute difference `|a - b|`.
+    Absolute,
+    /// Each output channel is `a - b`, clamped to the range of the channel type.
+    /// For unsigned channels this means negative differences become zero.
+    SignedClamped,
+    /// Each output channel is `a - b`, wrapping around at the bounds of the
+    /// channel type.
+    Wrapping,
+}
+
+/// Computes the per-channel difference `a - b` between two images,
+/// handling negative differences as specified by `mode`.
+///
+/// # Panics
+///
+/// If `a` and `b` do not have the same dimensions.
+///
+/// # Examples
+/// ```
+/// # extern crate image;
+/// # #[macro_use]
+/// # extern crate imageproc;
+/// # fn main() {
+/// use imageproc::map::{difference_image, DiffMode};
+///
+/// let a = gray_image!(10, 20);
+/// let b = gray_image!(15, 5);
+///
+/// assert_pixels_eq!(difference_image(&a, &b, DiffMode::Absolute), gray_image!(5, 15));
+/// assert_pixels_eq!(difference_image(&a, &b, DiffMode::SignedClamped), gray_image!(0, 15));
+/// assert_pixels_eq!(difference_image(&a, &b, DiffMode::Wrapping), gray_image!(251, 15));
+/// # }
+/// ```
+pub fn difference_image<P>(a: &Image<P>, b: &Image<P>, mode: DiffMode) -> Image<P>
+where
+    P: Pixel + 'static,
+    P::Subpixel: WrappingSub + ValueInto<f64> + Clamp<f64>,
+{
+    assert!(
+        a.dimensions() == b.dimensions(),
+        "images must have the same dimensions, but found {:?} and {:?}",
+        a.dimensions(),
+        b.dimensions()
+    );
+
+    map_colors2(a, b, |p, q| {
+        p.map2(&q, |x, y| match mode {
+            DiffMode::Absolute => P::Subpixel::clamp((cast(x) - cast(y)).abs()),
+            DiffMode::SignedClamped => P::Subpixel::clamp(cast(x) - cast(y)),
+            DiffMode::Wrapping => x.wrapping_sub(&y),
+        })
+    })
+}
+
 /// Applies `f` to each pixel in the input image.
 ///
 /// # Examples
@@ -267,6 +493,86 @@ where
     out
 }
 
+/// Splits the input image into non-overlapping blocks of size `block` and
+/// applies `f` to each block to compute a single output pixel.
+///
+/// `f` is passed the width and height of the block and its pixels in row-major
+/// order. If the image dimensions are not multiples of the block dimensions then
+/// the blocks at the right and bottom edges are smaller than `block`, and are
+/// passed to `f` with their actual size. The output image has dimensions
+/// `ceil(width / block.0)` by `ceil(height / block.1)`.
+///
+/// # Panics
+///
+/// If either block dimension is zero.
+///
+/// # Examples
+/// ```
+/// # extern crate image;
+/// # #[macro_use]
+/// # extern crate imageproc;
+/// # fn main() {
+/// use image::Luma;
+/// use imageproc::map::map_blocks;
+///
+/// let image = gray_image!(
+///     1, 3, 5;
+///     3, 5, 7);
+///
+/// let averaged = gray_image!(
+///     3, 6);
+///
+/// assert_pixels_eq!(
+///     map_blocks(&image, (2, 2), |w, h, ps| {
+///         let sum: u32 = ps.iter().map(|p| p[0] as u32).sum();
+///         Luma([(sum / (w * h)) as u8])
+///     }),
+///     averaged);
+/// # }
+/// ```
+pub fn map_blocks<I, P, Q, F>(image: &I, block: (u32, u32), f: F) -> Image<Q>
+where
+    I: GenericImage<Pixel = P>,
+    P: Pixel,
+    Q: Pixel + 'static,
+    F: Fn(u32, u32, &[P]) -> Q,
+{
+    let (block_width, block_height) = block;
+    assert!(
+        block_width > 0 && block_height > 0,
+        "block dimensions must be non-zero"
+    );
+
+    let (width, height) = image.dimensions();
+    let out_width = width / block_width + (width % block_width != 0) as u32;
+    let out_height = height / block_height + (height % block_height != 0) as u32;
+    let mut out: ImageBuffer<Q, Vec<Q::Subpixel>> = ImageBuffer::new(out_width, out_height);
+
+    let mut pixels = Vec::with_capacity((block_width * block_height) as usize);
+
+    for by in 0..out_height {
+        let top = by * block_height;
+        let h = block_height.min(height - top);
+        for bx in 0..out_width {
+            let left = bx * block_width;
+            let w = block_width.min(width - left);
+
+            pixels.clear();
+            for y in top..top + h {
+                for x in left..left + w {
+                    pixels.push(unsafe { image.unsafe_get_pixel(x, y) });
+                }
+            }
+
+            unsafe {
+                out.unsafe_put_pixel(bx, by, f(w, h, &pixels));
+            }
+        }
+    }
+
+    out
+}
+
 /// Creates a grayscale image by extracting the red channel of an RGB image.
 ///
 /// # Examples
@@ -1,12 +1,101 @@
 //! Functions for adding synthetic noise to images.
+//!
+//! The seeded functions in this module take a `u64` seed and use [`NoiseRng`], whose output
+//! depends only on the seed, so the noise they add is the same on 32-bit and 64-bit targets.
+//!
+//! [`NoiseRng`]: struct.NoiseRng.html
 
 use crate::definitions::{Clamp, HasBlack, HasWhite, Image};
 use crate::math::cast;
+use crate::rect::Rect;
 use conv::ValueInto;
-use image::Pixel;
-use rand::{rngs::StdRng, SeedableRng};
+use image::{GenericImageView, Luma, Pixel};
+use rand::{Error, Rng, RngCore, SeedableRng};
 use rand_distr::{Distribution, Normal, Uniform};
 
+/// The random number generator used by the seeded functions in this module.
+///
+/// This is the 32-bit output variant of the [PCG] family (PCG-XSH-RR with 64 bits of state).
+/// Its algorithm is fixed, so the sequence produced for a given seed does not depend on the
+/// version of the `rand` crate in use. Note that the transformation from this sequence to
+/// samples from a particular distribution is performed by `rand_distr`.
+///
+/// `NoiseRng` implements `RngCore` and `SeedableRng`, so can be passed to the `*_with_rng`
+/// functions in this module or used with any `rand` distribution.
+///
+/// [PCG]: https://www.pcg-random.org
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoiseRng {
+    state: u64,
+    increment: u64,
+}
+
+const PCG_MULTIPLIER: u64 = 6_364_136_223_846_793_005;
+const PCG_DEFAULT_STREAM: u64 = 0xda3e_39cb_94b9_5bdb;
+
+impl NoiseRng {
+    /// Creates a generator with the given initial state and stream selector, following the
+    /// initialisation used by the reference implementation's `pcg32_srandom_r`.
+    pub fn new(state: u64, stream: u64) -> NoiseRng {
+        let mut rng = NoiseRng {
+            state: 0,
+            increment: (stream << 1) | 1,
+        };
+        rng.step();
+        rng.state = rng.state.wrapping_add(state);
+        rng.step();
+        rng
+    }
+
+    fn step(&mut self) {
+        self.state = self
+            .state
+            .wrapping_mul(PCG_MULTIPLIER)
+            .wrapping_add(self.increment);
+    }
+}
+
+impl RngCore for NoiseRng {
+    fn next_u32(&mut self) -> u32 {
+        let old = self.state;
+        self.step();
+        let xor_shifted = (((old >> 18) ^ old) >> 27) as u32;
+        let rotation = (old >> 59) as u32;
+        xor_shifted.rotate_right(rotation)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let low = self.next_u32() as u64;
+        let high = self.next_u32() as u64;
+        (high << 32) | low
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(4) {
+            let bytes = self.next_u32().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl SeedableRng for NoiseRng {
+    type Seed = [u8; 8];
+
+    /// Uses the seed, read as a little-endian `u64`, as the initial state.
+    fn from_seed(seed: Self::Seed) -> NoiseRng {
+        NoiseRng::new(u64::from_le_bytes(seed), PCG_DEFAULT_STREAM)
+    }
+
+    fn seed_from_u64(seed: u64) -> NoiseRng {
+        NoiseRng::new(seed, PCG_DEFAULT_STREAM)
+    }
+}
+
 /// Adds independent additive Gaussian noise to all channels
 /// of an image, with the given mean and standard deviation.
 pub fn gaussian_noise<P>(image: &Image<P>, mean: f64, stddev: f64, seed: u64) -> Image<P>
@@ -26,13 +115,248 @@ where
     P: Pixel + 'static,
     P::Subpixel: ValueInto<f64> + Clamp<f64>,
 {
-    let mut rng: StdRng = SeedableRng::seed_from_u64(seed);
+    let mut rng = NoiseRng::seed_from_u64(seed);
+    gaussian_noise_mut_with_rng(image, mean, stddev, &mut rng);
+}
+
+/// Adds independent additive Gaussian noise to all channels of `src`, with the given mean
+/// and standard deviation, writing the result into `dst`.
+///
+/// The output is identical to that of [`gaussian_noise`] with the same arguments, but no
+/// buffer is allocated, so `dst` can be reused when repeatedly adding noise to images.
+///
+/// # Panics
+///
+/// If `src` and `dst` have different dimensions.
+///
+/// [`gaussian_noise`]: fn.gaussian_noise.html
+pub fn gaussian_noise_into<I, P>(src: &I, dst: &mut Image<P>, mean: f64, stddev: f64, seed: u64)
+where
+    I: GenericImageView<Pixel = P>,
+    P: Pixel + 'static,
+    P::Subpixel: ValueInto<f64> + Clamp<f64>,
+{
+    assert_eq!(
+        src.dimensions(),
+        dst.dimensions(),
+        "src and dst must have the same dimensions"
+    );
+    let mut rng = NoiseRng::seed_from_u64(seed);
     let normal = Normal::new(mean, stddev).unwrap();
 
+    for (x, y, p) in dst.enumerate_pixels_mut() {
+        *p = src.get_pixel(x, y);
+        for c in p.channels_mut() {
+            let noise = normal.sample(&mut rng);
+            *c = P::Subpixel::clamp(cast(*c) + noise);
+        }
+    }
+}
+
+/// Adds independent additive Gaussian noise to all channels of an image in place,
+/// with the given mean and standard deviation, using the provided random number generator.
+pub fn gaussian_noise_mut_with_rng<P, R>(image: &mut Image<P>, mean: f64, stddev: f64, rng: &mut R)
+where
+    P: Pixel + 'static,
+    P::Subpixel: ValueInto<f64> + Clamp<f64>,
+    R: Rng,
+{
+    let normal = Normal::new(mean, stddev).unwrap();
+
+    for p in image.pixels_mut() {
+        for c in p.channels_mut() {
+            let noise = normal.sample(rng);
+            *c = P::Subpixel::clamp(cast(*c) + noise);
+        }
+    }
+}
+
+/// Adds independent additive Gaussian noise to all channels of the pixels of an image
+/// within `rect` in place, with the given mean and standard deviation.
+///
+/// Noise is sampled for the pixels of `rect` in row-major order, so for a given `seed` the
+/// noise added at each offset from the top left of `rect` does not depend on the position of
+/// `rect`. Parts of `rect` outside the image are ignored, but noise is still sampled for them
+/// so that the noise added to the rest of `rect` is unchanged.
+pub fn gaussian_noise_in_rect_mut<P>(
+    image: &mut Image<P>,
+    rect: Rect,
+    mean: f64,
+    stddev: f64,
+    seed: u64,
+) where
+    P: Pixel + 'static,
+    P::Subpixel: ValueInto<f64> + Clamp<f64>,
+{
+    let mut rng = NoiseRng::seed_from_u64(seed);
+    let normal = Normal::new(mean, stddev).unwrap();
+    let (width, height) = image.dimensions();
+    let channels = P::CHANNEL_COUNT as usize;
+
+    for y in rect.top()..=rect.bottom() {
+        for x in rect.left()..=rect.right() {
+            if x < 0 || y < 0 || x as u32 >= width || y as u32 >= height {
+                for _ in 0..channels {
+                    normal.sample(&mut rng);
+                }
+                continue;
+            }
+            for c in image.get_pixel_mut(x as u32, y as u32).channels_mut() {
+                let noise = normal.sample(&mut rng);
+                *c = P::Subpixel::clamp(cast(*c) + noise);
+            }
+        }
+    }
+}
+
+/// Adds independent additive Gaussian noise to all channels of an image, with the
+/// given mean and standard deviation, truncated to within `truncate` standard deviations
+/// of the mean.
+///
+/// See [`gaussian_noise_truncated_mut`](fn.gaussian_noise_truncated_mut.html).
+pub fn gaussian_noise_truncated<P>(
+    image: &Image<P>,
+    mean: f64,
+    stddev: f64,
+    truncate: f64,
+    seed: u64,
+) -> Image<P>
+where
+    P: Pixel + 'static,
+    P::Subpixel: ValueInto<f64> + Clamp<f64>,
+{
+    let mut out = image.clone();
+    gaussian_noise_truncated_mut(&mut out, mean, stddev, truncate, seed);
+    out
+}
+
+/// Adds independent additive Gaussian noise to all channels of an image in place, with the
+/// given mean and standard deviation, truncated to within `truncate` standard deviations
+/// of the mean.
+///
+/// Noise values outside `mean ± truncate * stddev` are discarded and resampled, so the noise
+/// follows a truncated normal distribution rather than piling up at the bounds. To ensure
+/// termination when `truncate` is very small, each noise value is resampled at most
+/// 100 times, after which the last sample is clamped to the bounds.
+///
+/// # Panics
+///
+/// If `truncate` is not strictly positive, if `mean` or `stddev` is not finite, or if
+/// `stddev` is negative.
+pub fn gaussian_noise_truncated_mut<P>(
+    image: &mut Image<P>,
+    mean: f64,
+    stddev: f64,
+    truncate: f64,
+    seed: u64,
+) where
+    P: Pixel + 'static,
+    P::Subpixel: ValueInto<f64> + Clamp<f64>,
+{
+    const MAX_ATTEMPTS: usize = 100;
+
+    assert!(truncate > 0.0, "truncate must be strictly positive");
+    assert!(
+        mean.is_finite() && stddev.is_finite(),
+        "mean and stddev must be finite"
+    );
+    assert!(stddev >= 0.0, "stddev must be non-negative");
+    let mut rng = NoiseRng::seed_from_u64(seed);
+    let normal = Normal::new(mean, stddev).unwrap();
+    let bound = truncate * stddev;
+
     for p in image.pixels_mut() {
+        for c in p.channels_mut() {
+            let mut noise = normal.sample(&mut rng);
+            let mut attempts = 1;
+            while (noise - mean).abs() > bound && attempts < MAX_ATTEMPTS {
+                noise = normal.sample(&mut rng);
+                attempts += 1;
+            }
+            let noise = noise.max(mean - bound).min(mean + bound);
+            *c = P::Subpixel::clamp(cast(*c) + noise);
+        }
+    }
+}
+
+/// Adds independent additive Gaussian noise to all channels of an image, with the
+/// given mean and standard deviation, and also returns the noise that was sampled.
+///
+/// The returned image is identical to that produced by [`gaussian_noise`] with the same
+/// arguments. The noise image records, for each pixel, the sum over all channels of the
+/// noise values sampled for that pixel. These are the values before clamping, so
+/// may differ from the difference between the input and output images where the output
+/// saturated.
+///
+/// [`gaussian_noise`]: fn.gaussian_noise.html
+pub fn gaussian_noise_with_field<P>(
+    image: &Image<P>,
+    mean: f64,
+    stddev: f64,
+    seed: u64,
+) -> (Image<P>, Image<Luma<f32>>)
+where
+    P: Pixel + 'static,
+    P::Subpixel: ValueInto<f64> + Clamp<f64>,
+{
+    let mut out = image.clone();
+    let mut field = Image::new(image.width(), image.height());
+
+    let mut rng = NoiseRng::seed_from_u64(seed);
+    let normal = Normal::new(mean, stddev).unwrap();
+
+    for (p, f) in out.pixels_mut().zip(field.pixels_mut()) {
+        let mut total = 0.0;
         for c in p.channels_mut() {
             let noise = normal.sample(&mut rng);
             *c = P::Subpixel::clamp(cast(*c) + noise);
+            total += noise;
+        }
+        *f = Luma([total as f32]);
+    }
+
+    (out, field)
+}
+
+/// Adds independent additive Gaussian noise to all channels of a gamma-encoded image in place,
+/// with the noise applied to linear intensities rather than to the encoded values.
+///
+/// Each channel value `c` is converted to the linear intensity `max * (c / max).powf(gamma)`,
+/// where `max` is the channel value of `P::white()`, noise with the given mean and standard
+/// deviation is added, and the result is converted back using the inverse transform and then
+/// clamped. `mean` and `stddev` are therefore in linear units on the same scale as the channel
+/// values. Linear intensities are clamped to be non-negative before converting back.
+///
+/// This models sensor noise more realistically than [`gaussian_noise_mut`], which adds noise
+/// directly to the encoded values. A `gamma` of 2.2 approximates sRGB. With a `gamma` of 1.0
+/// the result is the same as for `gaussian_noise_mut` with the same `seed`.
+///
+/// # Panics
+///
+/// If `gamma` is not strictly positive.
+///
+/// [`gaussian_noise_mut`]: fn.gaussian_noise_mut.html
+pub fn gaussian_noise_linear_mut<P>(
+    image: &mut Image<P>,
+    mean: f64,
+    stddev: f64,
+    gamma: f64,
+    seed: u64,
+) where
+    P: Pixel + HasWhite + 'static,
+    P::Subpixel: ValueInto<f64> + Clamp<f64>,
+{
+    assert!(gamma > 0.0, "gamma must be strictly positive");
+    let mut rng = NoiseRng::seed_from_u64(seed);
+    let normal = Normal::new(mean, stddev).unwrap();
+    let max: f64 = cast(P::white().channels()[0]);
+
+    for p in image.pixels_mut() {
+        for c in p.channels_mut() {
+            let noise = normal.sample(&mut rng);
+            let linear = max * (cast(*c) / max).powf(gamma) + noise;
+            let encoded = max * (linear.max(0.0) / max).powf(1.0 / gamma);
+            *c = P::Subpixel::clamp(encoded);
         }
     }
 }
@@ -54,24 +378,216 @@ pub fn salt_and_pepper_noise_mut<P>(image: &mut Image<P>, rate: f64, seed: u64)
 where
     P: Pixel + HasBlack + HasWhite + 'static,
 {
-    let mut rng: StdRng = SeedableRng::seed_from_u64(seed);
+    let mut rng = NoiseRng::seed_from_u64(seed);
+    salt_and_pepper_noise_mut_with_rng(image, rate, &mut rng);
+}
+
+/// Converts pixels to black or white in place at the given `rate` (between 0.0 and 1.0),
+/// using the provided random number generator. Black and white occur with equal probability.
+pub fn salt_and_pepper_noise_mut_with_rng<P, R>(image: &mut Image<P>, rate: f64, rng: &mut R)
+where
+    P: Pixel + HasBlack + HasWhite + 'static,
+    R: Rng,
+{
+    salt_and_pepper_noise_impl(image, rate, rng);
+}
+
+/// Converts pixels to black or white in place at the given `rate` (between 0.0 and 1.0),
+/// and returns the number of pixels converted.
+///
+/// The noise added is identical to that added by [`salt_and_pepper_noise_mut`] for the
+/// same `seed`. The count includes pixels that already had the value they were set to.
+///
+/// [`salt_and_pepper_noise_mut`]: fn.salt_and_pepper_noise_mut.html
+pub fn salt_and_pepper_noise_mut_count<P>(image: &mut Image<P>, rate: f64, seed: u64) -> usize
+where
+    P: Pixel + HasBlack + HasWhite + 'static,
+{
+    let mut rng = NoiseRng::seed_from_u64(seed);
+    salt_and_pepper_noise_impl(image, rate, &mut rng)
+}
+
+fn salt_and_pepper_noise_impl<P, R>(image: &mut Image<P>, rate: f64, rng: &mut R) -> usize
+where
+    P: Pixel + HasBlack + HasWhite + 'static,
+    R: Rng,
+{
     let uniform = Uniform::new(0.0, 1.0);
+    let mut count = 0;
 
     for p in image.pixels_mut() {
-        if uniform.sample(&mut rng) > rate {
+        if uniform.sample(rng) > rate {
             continue;
         }
-        let r = uniform.sample(&mut rng);
+        let r = uniform.sample(rng);
         *p = if r >= 0.5 { P::white() } else { P::black() };
+        count += 1;
     }
+
+    count
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use image::GrayImage;
+    use image::{GrayImage, ImageBuffer, Luma, Rgb};
     use test::{black_box, Bencher};
 
+    #[test]
+    fn test_noise_rng_matches_pcg32_reference() {
+        // First outputs of the PCG reference implementation's pcg32-demo,
+        // seeded with pcg32_srandom(42, 54).
+        let mut rng = NoiseRng::new(42, 54);
+        let expected = [
+            0xa15c02b7, 0x7b47f409, 0xba1d3330, 0x83d2f293, 0xbfa4784b, 0xcbed606e,
+        ];
+        for &e in &expected {
+            assert_eq!(rng.next_u32(), e);
+        }
+    }
+
+    #[test]
+    fn test_gaussian_noise_mut_with_rng_matches_seeded() {
+        let image = GrayImage::from_pixel(10, 10, Luma([100u8]));
+        let mut with_rng = image.clone();
+        gaussian_noise_mut_with_rng(&mut with_rng, 0.0, 5.0, &mut NoiseRng::seed_from_u64(3));
+        assert_pixels_eq!(with_rng, gaussian_noise(&image, 0.0, 5.0, 3));
+    }
+
+    #[test]
+    fn test_gaussian_noise_into_matches_gaussian_noise() {
+        let image = GrayImage::from_fn(10, 8, |x, y| Luma([(10 * x + y) as u8]));
+        let mut dst = GrayImage::new(10, 8);
+        for seed in 0..3 {
+            gaussian_noise_into(&image, &mut dst, 0.0, 5.0, seed);
+            assert_pixels_eq!(dst, gaussian_noise(&image, 0.0, 5.0, seed));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gaussian_noise_into_rejects_mismatched_dimensions() {
+        let mut dst = GrayImage::new(10, 9);
+        gaussian_noise_into(&GrayImage::new(10, 8), &mut dst, 0.0, 5.0, 1);
+    }
+
+    #[test]
+    fn test_gaussian_noise_in_rect_mut() {
+        let image = GrayImage::from_pixel(20, 20, Luma([100u8]));
+
+        let mut whole = image.clone();
+        gaussian_noise_in_rect_mut(&mut whole, Rect::at(0, 0).of_size(20, 20), 0.0, 5.0, 2);
+        assert_pixels_eq!(whole, gaussian_noise(&image, 0.0, 5.0, 2));
+
+        let mut first = image.clone();
+        gaussian_noise_in_rect_mut(&mut first, Rect::at(2, 3).of_size(6, 4), 0.0, 5.0, 2);
+        let mut second = image.clone();
+        gaussian_noise_in_rect_mut(&mut second, Rect::at(12, 15).of_size(6, 4), 0.0, 5.0, 2);
+        // Partly outside the image, with the top left 4 x 1 pixels of the rect cut off.
+        let mut clipped = image.clone();
+        gaussian_noise_in_rect_mut(&mut clipped, Rect::at(-4, -1).of_size(6, 4), 0.0, 5.0, 2);
+
+        for (x, y, p) in first.enumerate_pixels() {
+            let inside = x >= 2 && x < 8 && y >= 3 && y < 7;
+            if inside {
+                assert_eq!(second.get_pixel(x + 10, y + 12), p);
+                if x >= 6 && y >= 4 {
+                    assert_eq!(clipped.get_pixel(x - 6, y - 4), p);
+                }
+            } else {
+                assert_eq!(p[0], 100);
+            }
+        }
+        assert!(first.pixels().any(|p| p[0] != 100));
+    }
+
+    #[test]
+    fn test_gaussian_noise_truncated() {
+        let image = ImageBuffer::from_pixel(50, 50, Luma([1000u16]));
+        let noisy = gaussian_noise_truncated(&image, 10.0, 4.0, 1.5, 5);
+        assert!(noisy.pixels().all(|p| p[0] >= 1004 && p[0] <= 1016));
+        assert!(noisy.pixels().any(|p| p[0] < 1008));
+        assert!(noisy.pixels().any(|p| p[0] > 1012));
+    }
+
+    #[test]
+    fn test_gaussian_noise_truncated_terminates_for_tiny_truncate() {
+        let image = ImageBuffer::from_pixel(20, 20, Luma([1000u16]));
+        let noisy = gaussian_noise_truncated(&image, 10.0, 4.0, 1e-9, 5);
+        assert!(noisy.pixels().all(|p| p[0] == 1009 || p[0] == 1010));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gaussian_noise_truncated_rejects_nan_stddev() {
+        let image = ImageBuffer::from_pixel(2, 2, Luma([1000u16]));
+        let _ = gaussian_noise_truncated(&image, 10.0, f64::NAN, 1.0, 5);
+    }
+
+    #[test]
+    fn test_gaussian_noise_with_field_matches_gaussian_noise() {
+        let image = GrayImage::from_pixel(10, 10, Luma([100u8]));
+        let (noisy, field) = gaussian_noise_with_field(&image, 0.0, 5.0, 7);
+        assert_pixels_eq!(noisy, gaussian_noise(&image, 0.0, 5.0, 7));
+
+        for (n, f) in noisy.pixels().zip(field.pixels()) {
+            assert_eq!(n[0], <u8 as Clamp<f64>>::clamp(100.0 + f[0] as f64));
+        }
+    }
+
+    #[test]
+    fn test_gaussian_noise_linear_mut_gamma_one_matches_gaussian_noise() {
+        let image = GrayImage::from_fn(10, 10, |x, y| Luma([(20 * x + y) as u8]));
+        let mut linear = image.clone();
+        gaussian_noise_linear_mut(&mut linear, 0.0, 5.0, 1.0, 4);
+        assert_pixels_eq!(linear, gaussian_noise(&image, 0.0, 5.0, 4));
+    }
+
+    #[test]
+    fn test_gaussian_noise_linear_mut_affects_dark_pixels_more() {
+        // The encoding is steepest near black, so equal linear noise moves
+        // dark encoded values further than bright ones.
+        let spread = |value: u8| {
+            let mut image = GrayImage::from_pixel(50, 50, Luma([value]));
+            gaussian_noise_linear_mut(&mut image, 0.0, 2.0, 2.2, 9);
+            let (min, max) = image
+                .pixels()
+                .fold((255, 0), |(lo, hi), p| (lo.min(p[0]), hi.max(p[0])));
+            max - min
+        };
+        assert!(spread(40) > 2 * spread(220));
+    }
+
+    #[test]
+    fn test_salt_and_pepper_noise_mut_count() {
+        let image = GrayImage::from_pixel(100, 100, Luma([100u8]));
+        let mut counted = image.clone();
+        let count = salt_and_pepper_noise_mut_count(&mut counted, 0.2, 5);
+
+        assert_pixels_eq!(counted, salt_and_pepper_noise(&image, 0.2, 5));
+        assert_eq!(counted.pixels().filter(|p| p[0] != 100).count(), count);
+        assert!(count > 1800 && count < 2200);
+
+        let mut all = image.clone();
+        assert_eq!(salt_and_pepper_noise_mut_count(&mut all, 1.0, 5), 10000);
+    }
+
+    #[test]
+    fn test_salt_and_pepper_noise_luma_u16() {
+        let image = ImageBuffer::from_pixel(10, 10, Luma([1000u16]));
+        let noisy = salt_and_pepper_noise(&image, 1.0, 1);
+        assert!(noisy.pixels().all(|p| p[0] == 0 || p[0] == u16::MAX));
+    }
+
+    #[test]
+    fn test_salt_and_pepper_noise_rgb_f32() {
+        let image = ImageBuffer::from_pixel(10, 10, Rgb([0.5f32; 3]));
+        let noisy = salt_and_pepper_noise(&image, 1.0, 1);
+        assert!(noisy
+            .pixels()
+            .all(|p| *p == Rgb([0.0; 3]) || *p == Rgb([1.0; 3])));
+    }
+
     #[bench]
     fn bench_gaussian_noise_mut(b: &mut Bencher) {
         let mut image = GrayImage::new(100, 100);
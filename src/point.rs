@@ -1,10 +1,15 @@
 //! A 2d point type.
 
-use num::{Num, NumCast};
+use num::traits::Saturating;
+use num::{Float, Num, NumCast};
 use std::ops::{Add, AddAssign, Sub, SubAssign};
 
 /// A 2d point.
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+///
+/// Points with integer coordinates implement `Eq` and `Hash`, so can be used as keys in
+/// a `HashMap` or `HashSet`. Points with floating point coordinates do not, as `f32` and
+/// `f64` implement neither trait.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct Point<T> {
     /// x-coordinate.
     pub x: T,
@@ -49,6 +54,53 @@ impl<T: Num + Copy> SubAssign for Point<T> {
     }
 }
 
+impl<T: Saturating> Point<T> {
+    /// Adds `other` to `self`, saturating each coordinate at the numeric bounds of `T`
+    /// instead of overflowing.
+    ///
+    /// # Examples
+    /// ```
+    /// use imageproc::point::Point;
+    ///
+    /// let p = Point::new(i32::MAX - 1, 5);
+    /// assert_eq!(p.saturating_add(Point::new(10, -10)), Point::new(i32::MAX, -5));
+    /// ```
+    pub fn saturating_add(self, other: Point<T>) -> Point<T> {
+        Point::new(
+            self.x.saturating_add(other.x),
+            self.y.saturating_add(other.y),
+        )
+    }
+
+    /// Subtracts `other` from `self`, saturating each coordinate at the numeric bounds of `T`
+    /// instead of overflowing.
+    ///
+    /// # Examples
+    /// ```
+    /// use imageproc::point::Point;
+    ///
+    /// let p = Point::new(3u32, 20u32);
+    /// assert_eq!(p.saturating_sub(Point::new(5, 5)), Point::new(0, 15));
+    /// ```
+    pub fn saturating_sub(self, other: Point<T>) -> Point<T> {
+        Point::new(
+            self.x.saturating_sub(other.x),
+            self.y.saturating_sub(other.y),
+        )
+    }
+}
+
+impl<T: Float> Point<T> {
+    /// Linearly interpolates between `self` and `other`, returning `self` when `t` is 0
+    /// and `other` when `t` is 1.
+    pub fn lerp(&self, other: Point<T>, t: T) -> Point<T> {
+        Point::new(
+            self.x + t * (other.x - self.x),
+            self.y + t * (other.y - self.y),
+        )
+    }
+}
+
 impl<T: NumCast> Point<T> {
     /// Converts to a Point<f64>. Panics if the cast fails.
     pub(crate) fn to_f64(&self) -> Point<f64> {
@@ -62,28 +114,100 @@ impl<T: NumCast> Point<T> {
 }
 
 /// Returns the Euclidean distance between two points.
-pub(crate) fn distance<T: NumCast>(p: Point<T>, q: Point<T>) -> f64 {
+///
+/// When only comparing distances, [`distance_squared`] avoids the square root.
+///
+/// [`distance_squared`]: fn.distance_squared.html
+pub fn distance<T: NumCast>(p: Point<T>, q: Point<T>) -> f64 {
     distance_sq(p, q).sqrt()
 }
 
+/// Returns the square of the Euclidean distance between two points, computed in `T`.
+///
+/// This is valid for unsigned `T`, but may overflow if `T` is an integer type.
+/// Use [`distance`] when the actual length is needed.
+///
+/// [`distance`]: fn.distance.html
+///
+/// # Examples
+/// ```
+/// use imageproc::point::{distance, distance_squared, Point};
+///
+/// let p = Point::new(1u32, 7u32);
+/// let q = Point::new(4u32, 3u32);
+/// assert_eq!(distance_squared(p, q), 25);
+/// assert_eq!(distance(p, q), 5.0);
+/// ```
+pub fn distance_squared<T>(p: Point<T>, q: Point<T>) -> T
+where
+    T: Num + PartialOrd + Copy,
+{
+    let abs_diff = |a: T, b: T| if a > b { a - b } else { b - a };
+    let dx = abs_diff(p.x, q.x);
+    let dy = abs_diff(p.y, q.y);
+    dx * dx + dy * dy
+}
+
 /// Returns the square of the Euclidean distance between two points.
-pub(crate) fn distance_sq<T: NumCast>(p: Point<T>, q: Point<T>) -> f64 {
+fn distance_sq<T: NumCast>(p: Point<T>, q: Point<T>) -> f64 {
     let p = p.to_f64();
     let q = q.to_f64();
     (p.x - q.x).powf(2.0) + (p.y - q.y).powf(2.0)
 }
 
+/// Returns the mean of a set of points.
+///
+/// # Panics
+///
+/// If `points` is empty.
+pub fn centroid<T: NumCast + Copy>(points: &[Point<T>]) -> Point<f64> {
+    assert!(!points.is_empty(), "points must be non-empty");
+    let sum = points
+        .iter()
+        .fold(Point::new(0.0, 0.0), |acc, p| acc + p.to_f64());
+    let n = points.len() as f64;
+    Point::new(sum.x / n, sum.y / n)
+}
+
+/// Returns the covariance matrix `[[var(x), cov(x, y)], [cov(x, y), var(y)]]`
+/// of a set of points. This is the population covariance, i.e. sums are
+/// divided by the number of points.
+///
+/// # Panics
+///
+/// If `points` is empty.
+pub fn covariance<T: NumCast + Copy>(points: &[Point<T>]) -> [[f64; 2]; 2] {
+    let mean = centroid(points);
+    let (mut xx, mut xy, mut yy) = (0.0, 0.0, 0.0);
+    for p in points {
+        let d = p.to_f64() - mean;
+        xx += d.x * d.x;
+        xy += d.x * d.y;
+        yy += d.y * d.y;
+    }
+    let n = points.len() as f64;
+    [[xx / n, xy / n], [xy / n, yy / n]]
+}
+
 /// A fixed rotation. This struct exists solely to cache the values of `sin(theta)` and `cos(theta)` when
 /// applying a fixed rotation to multiple points.
+///
+/// A positive rotation appears counter-clockwise when displayed on an image, whose y-axis
+/// points downwards. This is the opposite of the convention used by [`Projection::rotate`]
+/// and by the `angle` of a [`RotatedRect`]: `Rotation::new(theta)` turns points in the same
+/// direction as `Projection::rotate(-theta)`.
+///
+/// [`Projection::rotate`]: ../geometric_transformations/struct.Projection.html#method.rotate
+/// [`RotatedRect`]: ../rect/struct.RotatedRect.html
 #[derive(Debug, Copy, Clone, PartialEq)]
-pub(crate) struct Rotation {
+pub struct Rotation {
     sin_theta: f64,
     cos_theta: f64,
 }
 
 impl Rotation {
     /// A rotation of `theta` radians.
-    pub(crate) fn new(theta: f64) -> Rotation {
+    pub fn new(theta: f64) -> Rotation {
         let (sin_theta, cos_theta) = theta.sin_cos();
         Rotation {
             sin_theta,
@@ -93,27 +217,118 @@ impl Rotation {
 }
 
 impl Point<f64> {
-    /// Rotates a point.
-    pub(crate) fn rotate(&self, rotation: Rotation) -> Point<f64> {
+    /// Rounds both coordinates to the nearest integer and converts to a `Point<T>`.
+    /// Panics if the cast fails.
+    pub fn round_to<T: NumCast>(&self) -> Point<T> {
+        Point::new(
+            T::from(self.x.round()).unwrap(),
+            T::from(self.y.round()).unwrap(),
+        )
+    }
+
+    /// Rounds both coordinates down and converts to a `Point<T>`.
+    /// Panics if the cast fails.
+    pub fn floor_to<T: NumCast>(&self) -> Point<T> {
+        Point::new(
+            T::from(self.x.floor()).unwrap(),
+            T::from(self.y.floor()).unwrap(),
+        )
+    }
+
+    /// Rounds both coordinates up and converts to a `Point<T>`.
+    /// Panics if the cast fails.
+    pub fn ceil_to<T: NumCast>(&self) -> Point<T> {
+        Point::new(
+            T::from(self.x.ceil()).unwrap(),
+            T::from(self.y.ceil()).unwrap(),
+        )
+    }
+
+    /// Rotates a point about the origin.
+    pub fn rotate(&self, rotation: Rotation) -> Point<f64> {
         let x = self.x * rotation.cos_theta + self.y * rotation.sin_theta;
         let y = self.y * rotation.cos_theta - self.x * rotation.sin_theta;
         Point::new(x, y)
     }
 
-    /// Inverts a rotation.
-    pub(crate) fn invert_rotation(&self, rotation: Rotation) -> Point<f64> {
+    /// Inverts a rotation about the origin.
+    pub fn invert_rotation(&self, rotation: Rotation) -> Point<f64> {
         let x = self.x * rotation.cos_theta - self.y * rotation.sin_theta;
         let y = self.y * rotation.cos_theta + self.x * rotation.sin_theta;
         Point::new(x, y)
     }
+
+    /// Rotates a point about `center`.
+    pub fn rotate_about(&self, center: Point<f64>, rotation: Rotation) -> Point<f64> {
+        (*self - center).rotate(rotation) + center
+    }
+
+    /// Returns the dot product of `self` and `other`, treated as vectors.
+    ///
+    /// # Examples
+    /// ```
+    /// use imageproc::point::Point;
+    ///
+    /// // Project (3, 4) onto the x-axis.
+    /// let p = Point::new(3.0, 4.0);
+    /// assert_eq!(p.dot(Point::new(1.0, 0.0)), 3.0);
+    /// ```
+    pub fn dot(&self, other: Point<f64>) -> f64 {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// Returns the z-component of the cross product of `self` and `other`, treated as vectors.
+    ///
+    /// This is positive if `other` is counter-clockwise from `self` in a coordinate system
+    /// whose y-axis points upwards, negative if it is clockwise, and zero if they are parallel.
+    ///
+    /// # Examples
+    /// ```
+    /// use imageproc::point::Point;
+    ///
+    /// let p = Point::new(1.0, 0.0);
+    /// assert_eq!(p.cross(Point::new(0.0, 2.0)), 2.0);
+    /// assert_eq!(p.cross(Point::new(0.0, -2.0)), -2.0);
+    /// assert_eq!(p.cross(Point::new(3.0, 0.0)), 0.0);
+    /// ```
+    pub fn cross(&self, other: Point<f64>) -> f64 {
+        self.x * other.y - self.y * other.x
+    }
+
+    /// Returns the length of `self`, treated as a vector.
+    pub fn norm(&self) -> f64 {
+        self.x.hypot(self.y)
+    }
+
+    /// Returns the unit vector in the direction of `self`, or `self` unchanged if it
+    /// has length zero.
+    ///
+    /// # Examples
+    /// ```
+    /// use imageproc::point::Point;
+    ///
+    /// assert_eq!(Point::new(3.0, 4.0).normalized(), Point::new(0.6, 0.8));
+    /// assert_eq!(Point::new(0.0, 0.0).normalized(), Point::new(0.0, 0.0));
+    /// ```
+    pub fn normalized(&self) -> Point<f64> {
+        let norm = self.norm();
+        if norm == 0.0 {
+            *self
+        } else {
+            Point::new(self.x / norm, self.y / norm)
+        }
+    }
 }
 
 /// A line of the form Ax + By + C = 0.
 #[derive(Debug, Copy, Clone, PartialEq)]
-pub(crate) struct Line {
-    a: f64,
-    b: f64,
-    c: f64,
+pub struct Line {
+    /// Coefficient of x.
+    pub a: f64,
+    /// Coefficient of y.
+    pub b: f64,
+    /// Constant term.
+    pub c: f64,
 }
 
 impl Line {
@@ -136,6 +351,89 @@ impl Line {
 mod tests {
     use super::*;
 
+    #[test]
+    fn point_rounding() {
+        let p = Point::new(1.5, -2.25);
+        assert_eq!(p.round_to::<i32>(), Point::new(2, -2));
+        assert_eq!(p.floor_to::<i32>(), Point::new(1, -3));
+        assert_eq!(p.ceil_to::<i32>(), Point::new(2, -2));
+    }
+
+    #[test]
+    #[should_panic]
+    fn point_rounding_panics_if_cast_fails() {
+        let _ = Point::new(-1.0, 0.0).round_to::<u32>();
+    }
+
+    #[test]
+    fn point_saturating_arithmetic() {
+        let p = Point::new(i32::MIN + 1, i32::MAX - 1);
+        assert_eq!(
+            p.saturating_add(Point::new(-5, 5)),
+            Point::new(i32::MIN, i32::MAX)
+        );
+        assert_eq!(
+            p.saturating_sub(Point::new(5, -5)),
+            Point::new(i32::MIN, i32::MAX)
+        );
+        assert_eq!(
+            p.saturating_add(Point::new(1, -1)),
+            Point::new(i32::MIN + 2, i32::MAX - 2)
+        );
+    }
+
+    #[test]
+    fn points_as_hash_keys() {
+        use std::collections::HashSet;
+        let points: HashSet<Point<i32>> =
+            vec![Point::new(1, 2), Point::new(2, 1), Point::new(1, 2)]
+                .into_iter()
+                .collect();
+        assert_eq!(points.len(), 2);
+        assert!(points.contains(&Point::new(2, 1)));
+    }
+
+    #[test]
+    fn centroid_and_covariance() {
+        let points = [
+            Point::new(0, 0),
+            Point::new(2, 0),
+            Point::new(2, 4),
+            Point::new(0, 4),
+        ];
+        assert_eq!(centroid(&points), Point::new(1.0, 2.0));
+        assert_eq!(covariance(&points), [[1.0, 0.0], [0.0, 4.0]]);
+
+        let diagonal = [Point::new(0, 0), Point::new(1, 1), Point::new(2, 2)];
+        let c = covariance(&diagonal);
+        assert_approx_eq!(c[0][1], 2.0 / 3.0, 1e-10);
+        assert_eq!(c[0][1], c[1][0]);
+    }
+
+    #[test]
+    fn distance_squared_matches_distance() {
+        let p = Point::new(-2, 5);
+        let q = Point::new(3, -7);
+        assert_eq!(distance_squared(p, q), 169);
+        assert_eq!(distance(p, q), 13.0);
+        assert_eq!(
+            distance_squared(Point::new(1.5, 0.0), Point::new(0.0, 2.0)),
+            6.25
+        );
+    }
+
+    #[test]
+    fn vector_operations() {
+        let p = Point::new(3.0, 4.0);
+        let q = Point::new(-8.0, 6.0);
+        assert_eq!(p.dot(q), 0.0);
+        assert_eq!(p.cross(q), 50.0);
+        assert_eq!(q.cross(p), -50.0);
+        assert_eq!(p.norm(), 5.0);
+        assert_eq!(q.normalized(), Point::new(-0.8, 0.6));
+        assert_approx_eq!(q.normalized().norm(), 1.0, 1e-12);
+    }
+
     #[test]
     fn line_from_points() {
         let p = Point::new(5.0, 7.0);
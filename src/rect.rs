@@ -1,5 +1,7 @@
 //! Basic manipulation of rectangles.
 
+use crate::point::Point;
+use num::{cast, NumCast};
 use std::cmp;
 
 /// A rectangular region of non-zero width and height.
@@ -82,6 +84,33 @@ impl Rect {
         self.height
     }
 
+    /// Returns the corners of rect as [top left, top right, bottom right, bottom left].
+    ///
+    /// As for [`right`](#method.right) and [`bottom`](#method.bottom), the corners are the
+    /// outermost pixels contained in rect, so a rect of width 1 has equal left and right corners.
+    /// The returned points can be passed to polygon functions such as
+    /// [`draw_polygon_mut`](../drawing/fn.draw_polygon_mut.html).
+    ///
+    /// # Examples
+    /// ```
+    /// use imageproc::point::Point;
+    /// use imageproc::rect::Rect;
+    ///
+    /// let r = Rect::at(4, 5).of_size(6, 7);
+    /// assert_eq!(
+    ///     r.corners(),
+    ///     [Point::new(4, 5), Point::new(9, 5), Point::new(9, 11), Point::new(4, 11)]
+    /// );
+    /// ```
+    pub fn corners(&self) -> [Point<i32>; 4] {
+        [
+            Point::new(self.left, self.top),
+            Point::new(self.right(), self.top),
+            Point::new(self.right(), self.bottom()),
+            Point::new(self.left, self.bottom()),
+        ]
+    }
+
     /// Returns the intersection of self and other, or none if they are are disjoint.
     ///
     /// # Examples
@@ -121,6 +150,26 @@ impl Rect {
             height: (bottom - top) as u32 + 1,
         })
     }
+
+    /// Returns the intersection of self with the bounds of an image of the
+    /// given dimensions, or none if they are disjoint.
+    ///
+    /// # Examples
+    /// ```
+    /// use imageproc::rect::Rect;
+    ///
+    /// let r = Rect::at(-2, 3).of_size(5, 10);
+    /// assert_eq!(r.clamp_to(8, 6), Some(Rect::at(0, 3).of_size(3, 3)));
+    ///
+    /// let r = Rect::at(8, 0).of_size(5, 5);
+    /// assert_eq!(r.clamp_to(8, 6), None);
+    /// ```
+    pub fn clamp_to(&self, width: u32, height: u32) -> Option<Rect> {
+        if width == 0 || height == 0 {
+            return None;
+        }
+        self.intersect(Rect::at(0, 0).of_size(width, height))
+    }
 }
 
 impl Region<i32> for Rect {
@@ -163,9 +212,106 @@ impl RectPosition {
     }
 }
 
+/// A rectangle which need not be axis-aligned, such as those returned by
+/// [`min_area_rect`](../geometry/fn.min_area_rect.html).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RotatedRect {
+    /// The centre of the rectangle.
+    pub center: Point<f32>,
+    /// The length of the rectangle's sides parallel to its rotated x-axis.
+    pub width: f32,
+    /// The length of the rectangle's sides parallel to its rotated y-axis.
+    pub height: f32,
+    /// The angle in radians from the image x-axis to the rectangle's x-axis. As the
+    /// image y-axis points downwards, positive angles appear as clockwise rotations.
+    ///
+    /// This is the convention used by [`Projection::rotate`]. It is the opposite of the
+    /// convention used by [`Rotation`]: rotating the image x-axis by `Rotation::new(-angle)`
+    /// gives the direction of the rectangle's x-axis.
+    ///
+    /// [`Projection::rotate`]: ../geometric_transformations/struct.Projection.html#method.rotate
+    /// [`Rotation`]: ../point/struct.Rotation.html
+    pub angle: f32,
+}
+
+impl RotatedRect {
+    /// Constructs a rotated rectangle from its corners, listed as
+    /// [top left, top right, bottom right, bottom left] in the rectangle's own
+    /// coordinate frame. This is the order returned by
+    /// [`min_area_rect`](../geometry/fn.min_area_rect.html).
+    ///
+    /// If the corners do not form a rectangle then the result is the rectangle with the
+    /// same centre whose top edge is parallel to `corners[0]` -> `corners[1]`, and whose
+    /// sides have the lengths of the first two edges.
+    pub fn from_corners<T: NumCast + Copy>(corners: &[Point<T>; 4]) -> RotatedRect {
+        let c: Vec<Point<f32>> = corners
+            .iter()
+            .map(|p| Point::new(cast(p.x).unwrap(), cast(p.y).unwrap()))
+            .collect();
+        let top = c[1] - c[0];
+        let side = c[2] - c[1];
+        RotatedRect {
+            center: Point::new(
+                (c[0].x + c[1].x + c[2].x + c[3].x) / 4.0,
+                (c[0].y + c[1].y + c[2].y + c[3].y) / 4.0,
+            ),
+            width: top.x.hypot(top.y),
+            height: side.x.hypot(side.y),
+            angle: top.y.atan2(top.x),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{Rect, Region};
+    use super::{Rect, Region, RotatedRect};
+    use crate::point::Point;
+
+    #[test]
+    fn test_rotated_rect_from_corners() {
+        let axis_aligned = RotatedRect::from_corners(&[
+            Point::new(1, 2),
+            Point::new(7, 2),
+            Point::new(7, 6),
+            Point::new(1, 6),
+        ]);
+        assert_eq!(
+            axis_aligned,
+            RotatedRect {
+                center: Point::new(4.0, 4.0),
+                width: 6.0,
+                height: 4.0,
+                angle: 0.0
+            }
+        );
+
+        let rotated = RotatedRect::from_corners(&[
+            Point::new(2, 0),
+            Point::new(4, 2),
+            Point::new(2, 4),
+            Point::new(0, 2),
+        ]);
+        assert_eq!(rotated.center, Point::new(2.0, 2.0));
+        assert!((rotated.width - 8f32.sqrt()).abs() < 1e-6);
+        assert!((rotated.height - 8f32.sqrt()).abs() < 1e-6);
+        assert!((rotated.angle - std::f32::consts::FRAC_PI_4).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_rotated_rect_angle_is_opposite_to_rotation() {
+        use crate::point::Rotation;
+
+        let rect = RotatedRect::from_corners(&[
+            Point::new(2, 0),
+            Point::new(4, 2),
+            Point::new(2, 4),
+            Point::new(0, 2),
+        ]);
+        let axis = Point::new(1.0, 0.0).rotate(Rotation::new(-rect.angle as f64));
+        let s = std::f64::consts::FRAC_1_SQRT_2;
+        assert!((axis.x - s).abs() < 1e-6);
+        assert!((axis.y - s).abs() < 1e-6);
+    }
 
     #[test]
     #[should_panic]
@@ -1,9 +1,12 @@
 //! Functions for finding and labelling connected components of an image.
 
-use image::{GenericImage, GenericImageView, ImageBuffer, Luma};
+use image::{GenericImage, GenericImageView, ImageBuffer, Luma, Pixel};
 
 use crate::definitions::Image;
+use crate::math::cast;
+use crate::rect::Rect;
 use crate::union_find::DisjointSetForest;
+use conv::ValueInto;
 use std::cmp;
 
 /// Determines which neighbors of a pixel we consider
@@ -242,15 +245,240 @@ where
     out
 }
 
+/// Returns the bounding box of each connected foreground component of an image.
+///
+/// Components are determined as in [`connected_components`], and the `Rect` at index `i`
+/// of the returned `Vec` is the bounding box of the component with label `i + 1`.
+///
+/// # Panics
+/// Panics if the image contains 2<sup>32</sup> or more pixels.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate image;
+/// # #[macro_use]
+/// # extern crate imageproc;
+/// # fn main() {
+/// use image::Luma;
+/// use imageproc::rect::Rect;
+/// use imageproc::region_labelling::{connected_component_rects, Connectivity};
+///
+/// let image = gray_image!(
+///     1, 0, 1, 1;
+///     0, 1, 1, 0;
+///     0, 0, 0, 0;
+///     0, 0, 0, 1);
+///
+/// assert_eq!(
+///     connected_component_rects(&image, Connectivity::Four, Luma([0u8])),
+///     vec![
+///         Rect::at(0, 0).of_size(1, 1),
+///         Rect::at(1, 0).of_size(3, 2),
+///         Rect::at(3, 3).of_size(1, 1),
+///     ]);
+/// # }
+/// ```
+///
+/// [`connected_components`]: fn.connected_components.html
+pub fn connected_component_rects<I>(
+    image: &I,
+    conn: Connectivity,
+    background: I::Pixel,
+) -> Vec<Rect>
+where
+    I: GenericImage,
+    I::Pixel: Eq,
+{
+    let labels = connected_components(image, conn, background);
+
+    // (min_x, min_y, max_x, max_y) for each label
+    let mut bounds: Vec<(u32, u32, u32, u32)> = vec![];
+    for (x, y, p) in labels.enumerate_pixels() {
+        let label = p[0] as usize;
+        if label == 0 {
+            continue;
+        }
+        if label > bounds.len() {
+            bounds.resize(label, (u32::MAX, u32::MAX, 0, 0));
+        }
+        let b = &mut bounds[label - 1];
+        b.0 = cmp::min(b.0, x);
+        b.1 = cmp::min(b.1, y);
+        b.2 = cmp::max(b.2, x);
+        b.3 = cmp::max(b.3, y);
+    }
+
+    bounds
+        .into_iter()
+        .map(|(min_x, min_y, max_x, max_y)| {
+            Rect::at(min_x as i32, min_y as i32).of_size(max_x - min_x + 1, max_y - min_y + 1)
+        })
+        .collect()
+}
+
+/// Replaces the region of pixels connected to `(x, y)` whose colours are within
+/// `tolerance` of the colour at `(x, y)` with `fill`.
+///
+/// A pixel is within `tolerance` of the seed colour if the absolute difference between
+/// each of its channels and the corresponding channel of the seed is at most `tolerance`.
+/// Pixels are compared against their colours before filling, so the result does not
+/// depend on `fill`. An explicit stack is used, so large regions do not cause stack overflow.
+///
+/// # Panics
+/// If `(x, y)` is not within the image bounds.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate image;
+/// # #[macro_use]
+/// # extern crate imageproc;
+/// # fn main() {
+/// use image::Luma;
+/// use imageproc::region_labelling::{flood_fill_mut, Connectivity};
+///
+/// let mut image = gray_image!(
+///     1, 2, 9, 1;
+///     2, 9, 1, 1;
+///     9, 1, 1, 1);
+///
+/// flood_fill_mut(&mut image, 0, 0, Luma([0u8]), 1.0, Connectivity::Four);
+///
+/// let filled = gray_image!(
+///     0, 0, 9, 1;
+///     0, 9, 1, 1;
+///     9, 1, 1, 1);
+///
+/// assert_pixels_eq!(image, filled);
+/// # }
+/// ```
+pub fn flood_fill_mut<I>(
+    image: &mut I,
+    x: u32,
+    y: u32,
+    fill: I::Pixel,
+    tolerance: f64,
+    conn: Connectivity,
+) where
+    I: GenericImage,
+    <I::Pixel as Pixel>::Subpixel: ValueInto<f64>,
+{
+    let (width, height) = image.dimensions();
+    assert!(
+        x < width && y < height,
+        "seed ({}, {}) is outside image of size {}x{}",
+        x,
+        y,
+        width,
+        height
+    );
+
+    let seed = image.get_pixel(x, y);
+    let matches_seed = |p: I::Pixel| {
+        p.channels()
+            .iter()
+            .zip(seed.channels())
+            .all(|(&c, &s)| (cast::<_, f64>(c) - cast::<_, f64>(s)).abs() <= tolerance)
+    };
+
+    let offsets: &[(i64, i64)] = match conn {
+        Connectivity::Four => &[(0, -1), (-1, 0), (1, 0), (0, 1)],
+        Connectivity::Eight => &[
+            (-1, -1),
+            (0, -1),
+            (1, -1),
+            (-1, 0),
+            (1, 0),
+            (-1, 1),
+            (0, 1),
+            (1, 1),
+        ],
+    };
+
+    let mut visited = vec![false; width as usize * height as usize];
+    let mut stack = vec![(x, y)];
+    visited[y as usize * width as usize + x as usize] = true;
+
+    while let Some((px, py)) = stack.pop() {
+        image.put_pixel(px, py, fill);
+        for &(dx, dy) in offsets {
+            let (nx, ny) = (px as i64 + dx, py as i64 + dy);
+            if nx < 0 || ny < 0 || nx >= width as i64 || ny >= height as i64 {
+                continue;
+            }
+            let (nx, ny) = (nx as u32, ny as u32);
+            let index = ny as usize * width as usize + nx as usize;
+            if !visited[index] && matches_seed(image.get_pixel(nx, ny)) {
+                visited[index] = true;
+                stack.push((nx, ny));
+            }
+        }
+    }
+}
+
+/// Returns, for each row of `image`, the maximal horizontal runs of pixels for which
+/// `predicate` holds. Each run is given as `(start, end)`, where `start` is the x-coordinate
+/// of its first pixel and `end` is one past the x-coordinate of its last pixel.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate image;
+/// # #[macro_use]
+/// # extern crate imageproc;
+/// # fn main() {
+/// use imageproc::region_labelling::row_runs;
+///
+/// let image = gray_image!(
+///     0, 1, 1, 0, 1;
+///     0, 0, 0, 0, 0;
+///     1, 1, 1, 1, 1);
+///
+/// let runs = row_runs(&image, |p| p[0] > 0);
+///
+/// assert_eq!(runs, vec![vec![(1, 3), (4, 5)], vec![], vec![(0, 5)]]);
+/// # }
+/// ```
+pub fn row_runs<I, F>(image: &I, predicate: F) -> Vec<Vec<(u32, u32)>>
+where
+    I: GenericImageView,
+    F: Fn(I::Pixel) -> bool,
+{
+    let (width, height) = image.dimensions();
+    let mut runs = Vec::with_capacity(height as usize);
+
+    for y in 0..height {
+        let mut row = Vec::new();
+        let mut start = None;
+        for x in 0..width {
+            match (start, predicate(image.get_pixel(x, y))) {
+                (None, true) => start = Some(x),
+                (Some(s), false) => {
+                    row.push((s, x));
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        if let Some(s) = start {
+            row.push((s, width));
+        }
+        runs.push(row);
+    }
+
+    runs
+}
+
 #[cfg(test)]
 mod tests {
     extern crate wasm_bindgen_test;
 
-    use super::connected_components;
     use super::Connectivity::{Eight, Four};
+    use super::{connected_components, flood_fill_mut, row_runs};
     use crate::definitions::{HasBlack, HasWhite};
     use ::test;
-    use image::{GrayImage, ImageBuffer, Luma};
+    use image::{GrayImage, ImageBuffer, Luma, Rgb, RgbImage};
     #[cfg(target_arch = "wasm32")]
     use wasm_bindgen_test::*;
 
@@ -303,6 +531,66 @@ mod tests {
         assert_eq!(max_component, Some(450u32));
     }
 
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_row_runs_chessboard() {
+        let runs = row_runs(&chessboard(4, 2), |p| p[0] == 255);
+        assert_eq!(runs, vec![vec![(0, 1), (2, 3)], vec![(1, 2), (3, 4)]]);
+
+        let empty = GrayImage::new(0, 3);
+        assert_eq!(row_runs(&empty, |_| true), vec![vec![]; 3]);
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_flood_fill_chessboard() {
+        let mut four = chessboard(4, 4);
+        flood_fill_mut(&mut four, 0, 0, Luma([7u8]), 0.0, Four);
+        assert_eq!(four.pixels().filter(|p| p[0] == 7).count(), 1);
+
+        let mut eight = chessboard(4, 4);
+        flood_fill_mut(&mut eight, 0, 0, Luma([7u8]), 0.0, Eight);
+        assert_eq!(eight.pixels().filter(|p| p[0] == 7).count(), 8);
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_flood_fill_fill_within_tolerance() {
+        // The fill colour matches the seed, so this only terminates if
+        // filled pixels are not revisited.
+        let mut image = GrayImage::from_pixel(500, 500, Luma([10u8]));
+        image.put_pixel(3, 3, Luma([50u8]));
+        flood_fill_mut(&mut image, 0, 0, Luma([11u8]), 2.0, Four);
+
+        let mut expected = GrayImage::from_pixel(500, 500, Luma([11u8]));
+        expected.put_pixel(3, 3, Luma([50u8]));
+        assert_pixels_eq!(image, expected);
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_flood_fill_rgb_tolerance() {
+        let mut image = RgbImage::from_pixel(3, 1, Rgb([100, 100, 100]));
+        image.put_pixel(1, 0, Rgb([100, 105, 100]));
+        image.put_pixel(2, 0, Rgb([100, 100, 100]));
+
+        let mut strict = image.clone();
+        flood_fill_mut(&mut strict, 0, 0, Rgb([0, 0, 0]), 4.0, Four);
+        assert_eq!(strict.get_pixel(0, 0), &Rgb([0, 0, 0]));
+        assert_eq!(strict.get_pixel(1, 0), &Rgb([100, 105, 100]));
+        assert_eq!(strict.get_pixel(2, 0), &Rgb([100, 100, 100]));
+
+        flood_fill_mut(&mut image, 0, 0, Rgb([0, 0, 0]), 5.0, Four);
+        assert!(image.pixels().all(|p| *p == Rgb([0, 0, 0])));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_flood_fill_seed_out_of_bounds() {
+        let mut image = GrayImage::new(3, 3);
+        flood_fill_mut(&mut image, 3, 0, Luma([1u8]), 0.0, Four);
+    }
+
     #[bench]
     fn bench_connected_components_eight_chessboard(b: &mut test::Bencher) {
         let image = chessboard(300, 300);
@@ -1,7 +1,9 @@
 //! Statistical properties of images.
 
 use crate::definitions::Image;
+use crate::drawing::rasterize_polygon;
 use crate::math::cast;
+use crate::point::Point;
 use conv::ValueInto;
 use image::{GenericImageView, GrayImage, Pixel, Primitive};
 use num::Bounded;
@@ -28,6 +30,28 @@ where
     ChannelHistogram { channels: hist }
 }
 
+/// Returns a vector of per-channel histograms for an image with 16 bits per channel.
+///
+/// Each channel is binned into 256 bins, with bin `i` counting the channel values
+/// in the range `[256 * i, 256 * (i + 1))`, i.e. values are binned by their most
+/// significant byte. For images with 8 bits per channel use [`histogram`].
+///
+/// [`histogram`]: fn.histogram.html
+pub fn binned_histogram<P>(image: &Image<P>) -> ChannelHistogram
+where
+    P: Pixel<Subpixel = u16> + 'static,
+{
+    let mut hist = vec![[0u32; 256]; P::CHANNEL_COUNT as usize];
+
+    for pix in image.pixels() {
+        for (i, c) in pix.channels().iter().enumerate() {
+            hist[i][(*c >> 8) as usize] += 1;
+        }
+    }
+
+    ChannelHistogram { channels: hist }
+}
+
 /// A set of per-channel cumulative histograms from an image with 8 bits per channel.
 pub struct CumulativeChannelHistogram {
     /// Per-channel cumulative histograms.
@@ -131,6 +155,92 @@ where
     20f64 * max.log(10f64) - 10f64 * mse.log(10f64)
 }
 
+/// Per-channel statistics of the pixels in a region of an image.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegionStats {
+    /// Number of pixels in the region.
+    pub area: u64,
+    /// Per-channel mean of the pixels in the region.
+    pub mean: Vec<f64>,
+    /// Per-channel population standard deviation of the pixels in the region.
+    pub stddev: Vec<f64>,
+}
+
+/// Returns the area and per-channel mean and standard deviation of the pixels of `image`
+/// inside the polygon `poly`.
+///
+/// A pixel is inside the polygon exactly when it is set in the mask returned by
+/// [`rasterize_polygon`], so the polygon's boundary is included. If no pixels are
+/// inside the polygon then `area` is 0 and all means and standard deviations are 0.
+///
+/// [`rasterize_polygon`]: ../drawing/fn.rasterize_polygon.html
+///
+/// # Examples
+/// ```
+/// # extern crate image;
+/// # #[macro_use]
+/// # extern crate imageproc;
+/// # fn main() {
+/// use imageproc::point::Point;
+/// use imageproc::stats::masked_stats;
+///
+/// let image = gray_image!(
+///     9, 9, 9, 9;
+///     9, 2, 4, 9;
+///     9, 2, 4, 9);
+///
+/// let poly = [Point::new(1, 1), Point::new(2, 1), Point::new(2, 2), Point::new(1, 2)];
+/// let stats = masked_stats(&image, &poly);
+///
+/// assert_eq!(stats.area, 4);
+/// assert_eq!(stats.mean, vec![3.0]);
+/// assert_eq!(stats.stddev, vec![1.0]);
+/// # }
+/// ```
+pub fn masked_stats<I>(image: &I, poly: &[Point<i32>]) -> RegionStats
+where
+    I: GenericImageView,
+    <I::Pixel as Pixel>::Subpixel: ValueInto<f64>,
+{
+    let (width, height) = image.dimensions();
+    let mask = rasterize_polygon(width, height, poly);
+    let channels = I::Pixel::CHANNEL_COUNT as usize;
+
+    let mut area = 0u64;
+    let mut sums = vec![0f64; channels];
+    let mut sums_squared = vec![0f64; channels];
+
+    for (x, y, m) in mask.enumerate_pixels() {
+        if m[0] == 0 {
+            continue;
+        }
+        area += 1;
+        for (i, c) in image.get_pixel(x, y).channels().iter().enumerate() {
+            let c: f64 = cast(*c);
+            sums[i] += c;
+            sums_squared[i] += c * c;
+        }
+    }
+
+    if area == 0 {
+        return RegionStats {
+            area,
+            mean: sums,
+            stddev: sums_squared,
+        };
+    }
+
+    let n = area as f64;
+    let mean: Vec<f64> = sums.iter().map(|s| s / n).collect();
+    let stddev = sums_squared
+        .iter()
+        .zip(&mean)
+        .map(|(s, m)| (s / n - m * m).max(0.0).sqrt())
+        .collect();
+
+    RegionStats { area, mean, stddev }
+}
+
 fn mean_squared_error<I, J, P>(left: &I, right: &J) -> f64
 where
     I: GenericImageView<Pixel = P>,
@@ -195,6 +305,41 @@ mod tests {
         assert!(b.iter().skip(4).all(|x| *x == 5));
     }
 
+    #[test]
+    fn test_masked_stats_rgb() {
+        let image = rgb_image!(
+            [1, 10, 0], [3, 10, 0], [50, 50, 50];
+            [50, 50, 50], [50, 50, 50], [50, 50, 50]);
+
+        let stats = masked_stats(&image, &[Point::new(0, 0), Point::new(1, 0)]);
+        assert_eq!(stats.area, 2);
+        assert_eq!(stats.mean, vec![2.0, 10.0, 0.0]);
+        assert_eq!(stats.stddev, vec![1.0, 0.0, 0.0]);
+
+        let outside = [Point::new(-5, -5), Point::new(-1, -5), Point::new(-1, -1)];
+        let stats = masked_stats(&image, &outside);
+        assert_eq!(stats.area, 0);
+        assert_eq!(stats.mean, vec![0.0; 3]);
+        assert_eq!(stats.stddev, vec![0.0; 3]);
+    }
+
+    #[test]
+    fn test_masked_stats_closed_and_degenerate_polygons() {
+        let image = gray_image!(
+            1, 2, 3;
+            4, 5, 6);
+
+        let open = [Point::new(0, 0), Point::new(2, 0), Point::new(2, 1)];
+        let closed = [open[0], open[1], open[2], open[0]];
+        assert_eq!(masked_stats(&image, &closed), masked_stats(&image, &open));
+
+        for poly in &[&[][..], &[Point::new(1, 1)][..], &[Point::new(1, 1); 2][..]] {
+            let stats = masked_stats(&image, poly);
+            assert_eq!(stats.area, 0);
+            assert_eq!(stats.mean, vec![0.0]);
+        }
+    }
+
     #[test]
     fn test_histogram() {
         let image = gray_image!(1u8, 2u8, 3u8, 2u8, 1u8);
@@ -233,6 +378,26 @@ mod tests {
         assert!(b.iter().skip(4).all(|x| *x == 0));
     }
 
+    #[test]
+    fn test_binned_histogram() {
+        let image = rgb_image!(type: u16,
+            [0, 255, 65535],
+            [256, 511, 512],
+            [1000, 255, 65280]
+        );
+
+        let hist = binned_histogram(&image);
+        let r = hist.channels[0];
+        let g = hist.channels[1];
+        let b = hist.channels[2];
+
+        assert_eq!(r[0..4], [1, 1, 0, 1]);
+        assert_eq!(g[0..2], [2, 1]);
+        assert_eq!(b[2], 1);
+        assert_eq!(b[255], 2);
+        assert_eq!(hist.channels.iter().flat_map(|c| c.iter()).sum::<u32>(), 9);
+    }
+
     #[test]
     fn test_root_mean_squared_error_grayscale() {
         let left = gray_image!(
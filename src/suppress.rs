@@ -107,6 +107,10 @@ where
 
 /// Returns all items which have the highest score in the
 /// (2 * radius + 1) square block centred on them. Ties are resolved lexicographically.
+///
+/// This performs non-maximum suppression on any type implementing `Position`
+/// and `Score`, for example the `Corner`s returned by the FAST detectors in
+/// the [`corners`](../corners/index.html) module.
 pub fn local_maxima<T>(ts: &[T], radius: u32) -> Vec<T>
 where
     T: Position + Score + Copy,
@@ -131,11 +135,7 @@ where
 
         let mut is_max = true;
         let row_lower = if radius > cy { 0 } else { cy - radius };
-        let row_upper = if cy + radius + 1 > height {
-            height
-        } else {
-            cy + radius + 1
-        };
+        let row_upper = cmp::min(height + 1, cy + radius + 1);
         for y in row_lower..row_upper {
             for c in &ts_by_row[y as usize] {
                 if c.x() + radius < cx {
@@ -173,6 +173,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::{local_maxima, suppress_non_maximum};
+    use crate::corners::Corner;
     use crate::definitions::{Position, Score};
     use crate::noise::gaussian_noise_mut;
     use crate::property_testing::GrayTestImage;
@@ -236,6 +237,28 @@ mod tests {
         assert_eq!(max, expected);
     }
 
+    #[test]
+    fn test_local_maxima_compares_items_in_last_row() {
+        let ts = vec![T::new(0, 0, 1f32), T::new(1, 0, 2f32), T::new(5, 0, 1f32)];
+        assert_eq!(
+            local_maxima(&ts, 1),
+            vec![T::new(1, 0, 2f32), T::new(5, 0, 1f32)]
+        );
+    }
+
+    #[test]
+    fn test_local_maxima_corners() {
+        let corners = vec![
+            Corner::new(3, 3, 10f32),
+            Corner::new(4, 4, 12f32),
+            Corner::new(20, 20, 5f32),
+        ];
+        assert_eq!(
+            local_maxima(&corners, 2),
+            vec![Corner::new(4, 4, 12f32), Corner::new(20, 20, 5f32)]
+        );
+    }
+
     #[bench]
     fn bench_local_maxima_dense(b: &mut Bencher) {
         let mut ts = vec![];
@@ -400,6 +423,10 @@ mod tests {
     fn test_step() {
         assert_eq!((0u32..5).step_by(4).collect::<Vec<u32>>(), vec![0, 4]);
         assert_eq!((0u32..4).step_by(4).collect::<Vec<u32>>(), vec![0]);
-        assert_eq!((4u32..4).step_by(4).collect::<Vec<u32>>(), vec![]);
+        // A bare vec![] is ambiguous here once serde_json is in the dependency graph.
+        assert_eq!(
+            (4u32..4).step_by(4).collect::<Vec<u32>>(),
+            Vec::<u32>::new()
+        );
     }
 }
@@ -1,9 +1,13 @@
 //! Functions for performing template matching.
 use crate::definitions::Image;
+use crate::geometric_transformations::{pad_image, BorderMode};
 use crate::integral_image::{integral_squared_image, sum_image_pixels};
 use crate::rect::Rect;
 use image::Primitive;
-use image::{GrayImage, Luma};
+use image::{GenericImageView, GrayImage, Luma, Pixel, SubImage};
+use num::{Float, NumCast};
+use std::borrow::Cow;
+use std::cmp::Ordering;
 
 /// Method used to compute the matching score between a template and an image region.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -38,8 +42,198 @@ pub fn match_template(
     template: &GrayImage,
     method: MatchTemplateMethod,
 ) -> Image<Luma<f32>> {
-    use image::GenericImageView;
+    match_template_impl(image, template, method)
+}
+
+/// As [`match_template`], but pads `image` using `border` so that the template can be placed
+/// partly outside it, and so may be larger than `image`.
+///
+/// `image` is padded by `template.width() - 1` pixels on the left and right and by
+/// `template.height() - 1` pixels on the top and bottom, and scores are computed as for
+/// `match_template` on the padded image. The padded pixels therefore contribute to scores
+/// exactly as if they were part of the image, including to the normalization terms for the
+/// normalized methods. With `BorderMode::Constant(Luma([0]))` pixels outside the image
+/// contribute nothing to `CrossCorrelation` scores.
+///
+/// The returned image has dimensions `image.width() + template.width() - 1` by
+/// `image.height() + template.height() - 1`. The entry at `(x, y)` is the score for the
+/// template placed with its top left corner at `(x - template.width() + 1,
+/// y - template.height() + 1)` in `image`, so only templates overlapping `image` by at least
+/// one pixel are scored. If `image` is empty then no template placement overlaps it. If
+/// additionally `template` has width or height 1 then the returned image is empty.
+///
+/// # Panics
+///
+/// If `template` is empty, or if `image` is empty, `border` is not `BorderMode::Constant`
+/// and the returned image would be non-empty.
+///
+/// # Examples
+/// ```
+/// # extern crate image;
+/// # #[macro_use]
+/// # extern crate imageproc;
+/// # fn main() {
+/// use image::Luma;
+/// use imageproc::geometric_transformations::BorderMode;
+/// use imageproc::template_matching::{match_template_padded, MatchTemplateMethod};
+///
+/// let image = gray_image!(
+///     1, 2;
+///     3, 4);
+/// let template = gray_image!(
+///     4, 0, 0;
+///     0, 0, 0);
+///
+/// // The template is wider than the image.
+/// let scores = match_template_padded(
+///     &image,
+///     &template,
+///     MatchTemplateMethod::CrossCorrelation,
+///     BorderMode::Constant(Luma([0])),
+/// );
+/// assert_pixels_eq!(
+///     scores,
+///     gray_image!(type: f32,
+///         0.0, 0.0, 0.0, 0.0;
+///         0.0, 0.0, 4.0, 8.0;
+///         0.0, 0.0, 12.0, 16.0)
+/// );
+/// # }
+/// ```
+///
+/// [`match_template`]: fn.match_template.html
+pub fn match_template_padded(
+    image: &GrayImage,
+    template: &GrayImage,
+    method: MatchTemplateMethod,
+    border: BorderMode<Luma<u8>>,
+) -> Image<Luma<f32>> {
+    let (template_width, template_height) = template.dimensions();
+    assert!(
+        template_width > 0 && template_height > 0,
+        "template must be non-empty"
+    );
+    let (x_pad, y_pad) = (template_width - 1, template_height - 1);
+    let (output_width, output_height) = (image.width() + x_pad, image.height() + y_pad);
+    if output_width == 0 || output_height == 0 {
+        return Image::new(output_width, output_height);
+    }
+    let padded = pad_image(image, y_pad, y_pad, x_pad, x_pad, border);
+    match_template(&padded, template, method)
+}
+
+/// As [`match_template`], but accepts images and templates of any 8-bit pixel type.
+///
+/// Both `image` and `template` are first converted to grayscale using `Pixel::to_luma`,
+/// which combines the colour channels using the Rec. 709 luma weights and ignores any alpha
+/// channel. The converted images are then matched as by `match_template`, so each score
+/// is a single value computed from the luma of all pixels under the template.
+///
+/// # Panics
+///
+/// If either dimension of `template` is not strictly less than the corresponding dimension
+/// of `image`.
+///
+/// [`match_template`]: fn.match_template.html
+pub fn match_template_luma<I, J>(
+    image: &I,
+    template: &J,
+    method: MatchTemplateMethod,
+) -> Image<Luma<f32>>
+where
+    I: GenericImageView,
+    I::Pixel: Pixel<Subpixel = u8>,
+    J: GenericImageView,
+    J::Pixel: Pixel<Subpixel = u8>,
+{
+    match_template(&to_luma(image), &to_luma(template), method)
+}
+
+fn to_luma<I>(image: &I) -> GrayImage
+where
+    I: GenericImageView,
+    I::Pixel: Pixel<Subpixel = u8>,
+{
+    GrayImage::from_fn(image.width(), image.height(), |x, y| {
+        image.get_pixel(x, y).to_luma()
+    })
+}
 
+/// As [`match_template`], but accumulates scores using `f64` rather than `f32`.
+///
+/// This is slower than `match_template`, but avoids the loss of precision
+/// that can occur when using large templates.
+///
+/// # Panics
+///
+/// If either dimension of `template` is not strictly less than the corresponding dimension
+/// of `image`.
+///
+/// [`match_template`]: fn.match_template.html
+pub fn match_template_f64(
+    image: &GrayImage,
+    template: &GrayImage,
+    method: MatchTemplateMethod,
+) -> Image<Luma<f64>> {
+    match_template_impl(image, template, method)
+}
+
+fn match_template_impl<T>(
+    image: &GrayImage,
+    template: &GrayImage,
+    method: MatchTemplateMethod,
+) -> Image<Luma<T>>
+where
+    T: Primitive + Float + 'static,
+{
+    let matcher = Matcher::new(image, template, method);
+    match_template_tiled_impl(&matcher, matcher.output_dimensions())
+}
+
+/// Computes the `SumOfSquaredErrors` score of `template` at each position in `image` exactly,
+/// using integer arithmetic throughout.
+///
+/// Unlike [`match_template`], this accepts any pixel type with unsigned integer channels,
+/// and the score at each position is the sum of the squared differences over all channels
+/// of all pixels under the template. Scores are accumulated in `u64`, so are exact provided
+/// that they do not overflow, which is guaranteed for 8-bit and 16-bit channels unless the
+/// template has more than 2<sup>32</sup> channel values.
+///
+/// The returned image has dimensions `image.width() - template.width() + 1` by
+/// `image.height() - template.height() + 1`.
+///
+/// # Panics
+///
+/// If either dimension of `template` is greater than the corresponding dimension of `image`.
+///
+/// # Examples
+/// ```
+/// # extern crate image;
+/// # #[macro_use]
+/// # extern crate imageproc;
+/// # fn main() {
+/// use imageproc::template_matching::match_template_sse_int;
+///
+/// let image = gray_image!(type: u16,
+///     1000, 4000;
+///     2000, 1000);
+/// let template = gray_image!(type: u16, 1001);
+///
+/// assert_pixels_eq!(
+///     match_template_sse_int(&image, &template),
+///     gray_image!(type: u64,
+///         1, 8994001;
+///         998001, 1)
+/// );
+/// # }
+/// ```
+///
+/// [`match_template`]: fn.match_template.html
+pub fn match_template_sse_int<P>(image: &Image<P>, template: &Image<P>) -> Image<Luma<u64>>
+where
+    P: Pixel + 'static,
+    P::Subpixel: Into<u64>,
+{
     let (image_width, image_height) = image.dimensions();
     let (template_width, template_height) = template.dimensions();
 
@@ -52,81 +246,725 @@ pub fn match_template(
         "image height must be greater than or equal to template height"
     );
 
-    let should_normalize = matches! { method,
-    MatchTemplateMethod::SumOfSquaredErrorsNormalized
-    | MatchTemplateMethod::CrossCorrelationNormalized };
-    let image_squared_integral = if should_normalize {
-        Some(integral_squared_image(&image))
-    } else {
-        None
-    };
-    let template_squared_sum = if should_normalize {
-        Some(sum_squares(&template))
-    } else {
-        None
-    };
-
-    let mut result = Image::new(
+    Image::from_fn(
         image_width - template_width + 1,
         image_height - template_height + 1,
+        |x, y| {
+            let mut score = 0u64;
+            for (dx, dy, t) in template.enumerate_pixels() {
+                let i = image.get_pixel(x + dx, y + dy);
+                for (&a, &b) in i.channels().iter().zip(t.channels()) {
+                    let diff = Into::<u64>::into(a).abs_diff(b.into());
+                    score += diff * diff;
+                }
+            }
+            Luma([score])
+        },
+    )
+}
+
+/// The region of an image that a template is compared against by [`slide_window`].
+///
+/// [`slide_window`]: fn.slide_window.html
+pub type WindowView<'a, P> = SubImage<&'a Image<P>>;
+
+/// Slides a `template` over an `image` and scores the match at each point using the
+/// function `f`, which is passed the region of `image` under the template and the template.
+///
+/// This allows matching with metrics not provided by [`MatchTemplateMethod`], for example
+/// a sum of absolute differences. The window and template always have the same dimensions.
+/// `f` is called once per output pixel, so unlike [`match_template`] no running sums are
+/// shared between positions.
+///
+/// The returned image has dimensions `image.width() - template.width() + 1` by
+/// `image.height() - template.height() + 1`.
+///
+/// # Panics
+///
+/// If either dimension of `template` is greater than the corresponding dimension of `image`.
+///
+/// [`MatchTemplateMethod`]: enum.MatchTemplateMethod.html
+/// [`match_template`]: fn.match_template.html
+pub fn slide_window<P, F>(image: &Image<P>, template: &Image<P>, f: F) -> Image<Luma<f32>>
+where
+    P: Pixel + 'static,
+    F: Fn(&WindowView<'_, P>, &Image<P>) -> f32,
+{
+    let (image_width, image_height) = image.dimensions();
+    let (template_width, template_height) = template.dimensions();
+
+    assert!(
+        image_width >= template_width,
+        "image width must be greater than or equal to template width"
+    );
+    assert!(
+        image_height >= template_height,
+        "image height must be greater than or equal to template height"
     );
 
-    for y in 0..result.height() {
-        for x in 0..result.width() {
-            let mut score = 0f32;
+    Image::from_fn(
+        image_width - template_width + 1,
+        image_height - template_height + 1,
+        |x, y| {
+            let window = image.view(x, y, template_width, template_height);
+            Luma([f(&window, template)])
+        },
+    )
+}
+
+/// As [`match_template`], but for the normalized methods returns the square of each score,
+/// which avoids taking a square root at every position.
+///
+/// All scores are non-negative, so squaring them preserves their order and the best match
+/// is at the same position as for `match_template`. However, the scores for the normalized
+/// methods are not directly comparable to those returned by `match_template`, or to
+/// thresholds chosen for them. Scores for the other methods are unchanged.
+///
+/// # Panics
+///
+/// If either dimension of `template` is not strictly less than the corresponding dimension
+/// of `image`.
+///
+/// [`match_template`]: fn.match_template.html
+pub fn match_template_ranked(
+    image: &GrayImage,
+    template: &GrayImage,
+    method: MatchTemplateMethod,
+) -> Image<Luma<f32>> {
+    let mut matcher = Matcher::new(image, template, method);
+    matcher.square_normalized_scores = true;
+    match_template_tiled_impl(&matcher, matcher.output_dimensions())
+}
+
+/// Slides a `template` over an `image` and scores the match at each point using
+/// the requested `method`, computing the output in tiles of size `tile`.
+///
+/// Each output tile reads the region of `image` that it covers, extended right and
+/// down by the template size. Processing the output in tiles can improve cache
+/// locality for very large images. The result is identical to that of [`match_template`].
+///
+/// # Panics
+///
+/// If either dimension of `template` is not strictly less than the corresponding dimension
+/// of `image`, or if either dimension of `tile` is zero.
+///
+/// [`match_template`]: fn.match_template.html
+pub fn match_template_tiled(
+    image: &GrayImage,
+    template: &GrayImage,
+    method: MatchTemplateMethod,
+    tile: (u32, u32),
+) -> Image<Luma<f32>> {
+    assert!(tile.0 > 0 && tile.1 > 0, "tile dimensions must be non-zero");
+    let matcher = Matcher::new(image, template, method);
+    match_template_tiled_impl(&matcher, tile)
+}
+
+/// Slides each of `templates` over an `image` and scores the match at each point using
+/// the requested `method`.
+///
+/// The result is identical to calling [`match_template`] for each template in turn, but
+/// when `method` is normalized the integral squared image of `image` is computed only once
+/// and shared between all templates. The returned score maps are in the same order as
+/// `templates`.
+///
+/// # Panics
+///
+/// If either dimension of any template is not strictly less than the corresponding dimension
+/// of `image`.
+///
+/// [`match_template`]: fn.match_template.html
+pub fn match_templates(
+    image: &GrayImage,
+    templates: &[&GrayImage],
+    method: MatchTemplateMethod,
+) -> Vec<Image<Luma<f32>>> {
+    use MatchTemplateMethod::*;
+
+    let image_squared_integral = match method {
+        SumOfSquaredErrorsNormalized | CrossCorrelationNormalized => {
+            Some(integral_squared_image(image))
+        }
+        SumOfSquaredErrors | CrossCorrelation => None,
+    };
+
+    templates
+        .iter()
+        .map(|template| {
+            let matcher = Matcher::with_options(
+                image,
+                template,
+                method,
+                None,
+                true,
+                image_squared_integral.as_ref(),
+            );
+            match_template_tiled_impl(&matcher, matcher.output_dimensions())
+        })
+        .collect()
+}
+
+/// Scores the match of a `template` against an `image` only at positions whose
+/// coordinates are multiples of `step`, for use in coarse-to-fine searches.
+///
+/// The entry at `(x, y)` in the returned image is the score for the template placed with
+/// its top left corner at `(step * x, step * y)`, and is identical to the corresponding
+/// entry in the output of [`match_template`]. If `match_template` would return an image of
+/// size `w` by `h` then the returned image has size `ceil(w / step)` by `ceil(h / step)`.
+///
+/// # Panics
+///
+/// If either dimension of `template` is not strictly less than the corresponding dimension
+/// of `image`, or if `step` is zero.
+///
+/// # Examples
+///
+/// A coarse search followed by refinement around the best coarse match, using
+/// [`score_positions`].
+/// ```
+/// use image::{GrayImage, Luma};
+/// use imageproc::template_matching::{
+///     find_extremes, match_template_strided, score_positions, MatchTemplateMethod,
+/// };
+///
+/// let image = GrayImage::from_fn(40, 40, |x, y| {
+///     let (dx, dy) = (x as i32 - 21, y as i32 - 14);
+///     Luma([(255 - 8 * (dx * dx + dy * dy)).max(0) as u8])
+/// });
+/// let template = GrayImage::from_fn(5, 5, |x, y| *image.get_pixel(x + 19, y + 12));
+/// let method = MatchTemplateMethod::SumOfSquaredErrors;
+///
+/// // Back-project the best coarse match by multiplying by the step.
+/// let step = 4;
+/// let coarse = match_template_strided(&image, &template, method, step);
+/// let (cx, cy) = find_extremes(&coarse).min_value_location;
+/// let (cx, cy) = (cx * step, cy * step);
+///
+/// // Search the full resolution scores within step pixels of the coarse match.
+/// let candidates: Vec<(u32, u32)> = (cy.saturating_sub(step)..=cy + step)
+///     .flat_map(|y| (cx.saturating_sub(step)..=cx + step).map(move |x| (x, y)))
+///     .filter(|&(x, y)| x <= 35 && y <= 35)
+///     .collect();
+/// let scores = score_positions(&image, &template, &candidates, method);
+/// let best = (0..scores.len())
+///     .min_by(|&i, &j| scores[i].partial_cmp(&scores[j]).unwrap())
+///     .unwrap();
+/// assert_eq!(candidates[best], (19, 12));
+/// ```
+///
+/// [`match_template`]: fn.match_template.html
+/// [`score_positions`]: fn.score_positions.html
+pub fn match_template_strided(
+    image: &GrayImage,
+    template: &GrayImage,
+    method: MatchTemplateMethod,
+    step: u32,
+) -> Image<Luma<f32>> {
+    assert!(step > 0, "step must be non-zero");
+    let matcher = Matcher::new(image, template, method);
+    let (width, height) = matcher.output_dimensions();
+    let strided_width = width / step + (width % step != 0) as u32;
+    let strided_height = height / step + (height % step != 0) as u32;
+
+    Image::from_fn(strided_width, strided_height, |x, y| {
+        Luma([matcher.score_at(x * step, y * step)])
+    })
+}
 
-            for dy in 0..template_height {
-                for dx in 0..template_width {
-                    let image_value = unsafe { image.unsafe_get_pixel(x + dx, y + dy)[0] as f32 };
-                    let template_value = unsafe { template.unsafe_get_pixel(dx, dy)[0] as f32 };
+/// Scores the match of a `template` against an `image` only at the given `positions`,
+/// for refining candidates found by a coarse search.
+///
+/// Each position is the location of the template's top left corner, and the score
+/// returned for it is identical to the corresponding entry in the output of
+/// [`match_template`]. Scores are returned in the same order as `positions`.
+///
+/// The image is not preprocessed, so the cost is proportional to the number of positions
+/// times the template area for every method, independent of the size of `image`.
+///
+/// # Panics
+///
+/// If either dimension of `template` is not strictly less than the corresponding dimension
+/// of `image`, or if any position would place the template partly outside `image`.
+///
+/// # Examples
+/// ```
+/// # extern crate image;
+/// # #[macro_use]
+/// # extern crate imageproc;
+/// # fn main() {
+/// use imageproc::template_matching::{score_positions, MatchTemplateMethod};
+///
+/// let image = gray_image!(
+///     1, 4, 2;
+///     2, 1, 3;
+///     3, 3, 4);
+/// let template = gray_image!(
+///     1, 2;
+///     3, 4);
+///
+/// let scores = score_positions(
+///     &image,
+///     &template,
+///     &[(1, 1), (0, 0)],
+///     MatchTemplateMethod::SumOfSquaredErrors,
+/// );
+/// assert_eq!(scores, vec![1.0, 14.0]);
+/// # }
+/// ```
+///
+/// [`match_template`]: fn.match_template.html
+pub fn score_positions(
+    image: &GrayImage,
+    template: &GrayImage,
+    positions: &[(u32, u32)],
+    method: MatchTemplateMethod,
+) -> Vec<f32> {
+    let matcher = Matcher::without_integral(image, template, method);
+    let (width, height) = matcher.output_dimensions();
 
-                    use MatchTemplateMethod::*;
+    positions
+        .iter()
+        .map(|&(x, y)| {
+            assert!(
+                x < width && y < height,
+                "position ({}, {}) places the template outside the image",
+                x,
+                y
+            );
+            matcher.score_at(x, y)
+        })
+        .collect()
+}
 
-                    score += match method {
+/// As [`match_template`], but template pixels equal to `ignore_value` do not contribute
+/// to the score.
+///
+/// This is useful when parts of the template are unreliable, for example when matching
+/// under glare a value of 255 can be used to ignore saturated template pixels. For the
+/// normalized methods the normalization term is also computed using only the image and
+/// template pixels at positions where the template is not equal to `ignore_value`.
+///
+/// # Panics
+///
+/// If either dimension of `template` is not strictly less than the corresponding dimension
+/// of `image`.
+///
+/// [`match_template`]: fn.match_template.html
+pub fn match_template_ignoring_value(
+    image: &GrayImage,
+    template: &GrayImage,
+    method: MatchTemplateMethod,
+    ignore_value: u8,
+) -> Image<Luma<f32>> {
+    let matcher = Matcher::with_ignore_value(image, template, method, Some(ignore_value));
+    match_template_tiled_impl(&matcher, matcher.output_dimensions())
+}
+
+/// As [`match_template`], but also returns the normalization term computed at each
+/// position, which can be useful when debugging unexpected normalized scores.
+///
+/// For the normalized methods, the second returned image contains the square root of the
+/// product of the sums of squared intensities of the template and of the image region
+/// it overlaps. Scores are divided by this term unless it is zero, in which case they
+/// are left unnormalized. For the unnormalized methods no such term is used, and the
+/// second image is filled with ones.
+///
+/// # Panics
+///
+/// If either dimension of `template` is not strictly less than the corresponding dimension
+/// of `image`.
+///
+/// [`match_template`]: fn.match_template.html
+pub fn match_template_with_norm(
+    image: &GrayImage,
+    template: &GrayImage,
+    method: MatchTemplateMethod,
+) -> (Image<Luma<f32>>, Image<Luma<f32>>) {
+    let matcher = Matcher::new(image, template, method);
+    let (width, height) = matcher.output_dimensions();
+    let mut scores = Image::new(width, height);
+    let mut norms = Image::new(width, height);
+
+    for y in 0..height {
+        for x in 0..width {
+            let (score, norm) = matcher.score_and_norm_at(x, y);
+            scores.put_pixel(x, y, Luma([score]));
+            norms.put_pixel(x, y, Luma([norm.unwrap_or(1.0)]));
+        }
+    }
+
+    (scores, norms)
+}
+
+fn match_template_tiled_impl<T>(matcher: &Matcher<'_, T>, tile: (u32, u32)) -> Image<Luma<T>>
+where
+    T: Primitive + Float + 'static,
+{
+    let (width, height) = matcher.output_dimensions();
+    let (tile_width, tile_height) = tile;
+    let mut result = Image::new(width, height);
+
+    for tile_y in (0..height).step_by(tile_height as usize) {
+        for tile_x in (0..width).step_by(tile_width as usize) {
+            for y in tile_y..(tile_y + tile_height).min(height) {
+                for x in tile_x..(tile_x + tile_width).min(width) {
+                    result.put_pixel(x, y, Luma([matcher.score_at(x, y)]));
+                }
+            }
+        }
+    }
+
+    result
+}
+
+/// Computes the match score between a template and the image region at a given location.
+struct Matcher<'a, T> {
+    image: &'a GrayImage,
+    template: &'a GrayImage,
+    method: MatchTemplateMethod,
+    /// Template pixels with this value do not contribute to the score.
+    ignore_value: Option<u8>,
+    image_squared_integral: Option<Cow<'a, Image<Luma<u64>>>>,
+    template_squared_sum: Option<T>,
+    /// If true then `score_at` returns the square of the normalized score for the
+    /// normalized methods, avoiding a square root.
+    square_normalized_scores: bool,
+}
+
+impl<'a, T: Float> Matcher<'a, T> {
+    fn new(image: &'a GrayImage, template: &'a GrayImage, method: MatchTemplateMethod) -> Self {
+        Self::with_ignore_value(image, template, method, None)
+    }
+
+    fn with_ignore_value(
+        image: &'a GrayImage,
+        template: &'a GrayImage,
+        method: MatchTemplateMethod,
+        ignore_value: Option<u8>,
+    ) -> Self {
+        Self::with_options(image, template, method, ignore_value, true, None)
+    }
+
+    /// As `new`, but for the normalized methods computes the image sum of squares directly
+    /// at each position rather than from an integral image. This avoids a pass over the
+    /// whole image when only a few positions are scored.
+    fn without_integral(
+        image: &'a GrayImage,
+        template: &'a GrayImage,
+        method: MatchTemplateMethod,
+    ) -> Self {
+        Self::with_options(image, template, method, None, false, None)
+    }
+
+    /// If `use_integral` is false then image sums of squares are computed directly at
+    /// each position. Otherwise, if `image_squared_integral` is provided then it must be
+    /// the integral squared image of `image`, and is used in place of computing it.
+    fn with_options(
+        image: &'a GrayImage,
+        template: &'a GrayImage,
+        method: MatchTemplateMethod,
+        ignore_value: Option<u8>,
+        use_integral: bool,
+        image_squared_integral: Option<&'a Image<Luma<u64>>>,
+    ) -> Self {
+        let (image_width, image_height) = image.dimensions();
+        let (template_width, template_height) = template.dimensions();
+
+        assert!(
+            image_width >= template_width,
+            "image width must be greater than or equal to template width"
+        );
+        assert!(
+            image_height >= template_height,
+            "image height must be greater than or equal to template height"
+        );
+
+        let should_normalize = matches! { method,
+        MatchTemplateMethod::SumOfSquaredErrorsNormalized
+        | MatchTemplateMethod::CrossCorrelationNormalized };
+        // When some template pixels are ignored the image sum of squares must be
+        // computed over the remaining pixels only, so an integral image is no use.
+        let image_squared_integral = if should_normalize && use_integral && ignore_value.is_none() {
+            Some(match image_squared_integral {
+                Some(integral) => Cow::Borrowed(integral),
+                None => Cow::Owned(integral_squared_image(image)),
+            })
+        } else {
+            None
+        };
+        let template_squared_sum = if should_normalize {
+            Some(sum_squares::<T>(template, ignore_value))
+        } else {
+            None
+        };
+
+        Matcher {
+            image,
+            template,
+            method,
+            ignore_value,
+            image_squared_integral,
+            template_squared_sum,
+            square_normalized_scores: false,
+        }
+    }
+
+    /// Dimensions of the score map for this image and template.
+    fn output_dimensions(&self) -> (u32, u32) {
+        (
+            self.image.width() - self.template.width() + 1,
+            self.image.height() - self.template.height() + 1,
+        )
+    }
+
+    /// Score for the template placed with its top left corner at (x, y).
+    /// The caller must ensure that (x, y) is within `output_dimensions()`.
+    fn score_at(&self, x: u32, y: u32) -> T {
+        if !self.square_normalized_scores {
+            return self.score_and_norm_at(x, y).0;
+        }
+        let (score, norm_squared) = self.unnormalized_score_at(x, y);
+        match norm_squared {
+            Some(n) if n > T::zero() => score * score / n,
+            Some(_) => score * score,
+            None => score,
+        }
+    }
+
+    /// As `score_at`, but also returns the normalization term for this position
+    /// if `method` is one of the normalized methods.
+    fn score_and_norm_at(&self, x: u32, y: u32) -> (T, Option<T>) {
+        let (mut score, norm_squared) = self.unnormalized_score_at(x, y);
+        let norm = norm_squared.map(|n| n.sqrt());
+        if let Some(norm) = norm {
+            if norm > T::zero() {
+                score = score / norm;
+            }
+        }
+        (score, norm)
+    }
+
+    /// Returns the score for this position before normalization, and the square of the
+    /// normalization term if `method` is one of the normalized methods.
+    fn unnormalized_score_at(&self, x: u32, y: u32) -> (T, Option<T>) {
+        use image::GenericImageView;
+
+        let (template_width, template_height) = self.template.dimensions();
+        let mut score = T::zero();
+        // Accumulated exactly, so that the result matches that computed from an integral image.
+        let mut image_squared_sum = 0u64;
+        let sum_image_squares =
+            self.template_squared_sum.is_some() && self.image_squared_integral.is_none();
+
+        for dy in 0..template_height {
+            for dx in 0..template_width {
+                let template_pixel = unsafe { self.template.unsafe_get_pixel(dx, dy)[0] };
+                if self.ignore_value == Some(template_pixel) {
+                    continue;
+                }
+                let image_pixel = unsafe { self.image.unsafe_get_pixel(x + dx, y + dy)[0] };
+                let image_value = to_float::<T, _>(image_pixel);
+                let template_value = to_float::<T, _>(template_pixel);
+                if sum_image_squares {
+                    image_squared_sum += image_pixel as u64 * image_pixel as u64;
+                }
+
+                use MatchTemplateMethod::*;
+
+                score = score
+                    + match self.method {
                         SumOfSquaredErrors | SumOfSquaredErrorsNormalized => {
-                            (image_value - template_value).powf(2.0)
+                            (image_value - template_value).powi(2)
                         }
                         CrossCorrelation | CrossCorrelationNormalized => {
                             image_value * template_value
                         }
                     };
-                }
             }
+        }
 
-            if let (&Some(ref i), &Some(t)) = (&image_squared_integral, &template_squared_sum) {
-                let region = Rect::at(x as i32, y as i32).of_size(template_width, template_height);
-                let norm = normalization_term(i, t, region);
-                if norm > 0.0 {
-                    score /= norm;
+        let norm_squared = self
+            .template_squared_sum
+            .map(|t| match self.image_squared_integral {
+                Some(ref i) => {
+                    let region =
+                        Rect::at(x as i32, y as i32).of_size(template_width, template_height);
+                    squared_normalization_term(i, t, region)
                 }
-            }
+                None => to_float::<T, _>(image_squared_sum) * t,
+            });
 
-            result.put_pixel(x, y, Luma([score]));
-        }
+        (score, norm_squared)
     }
+}
 
-    result
+fn to_float<T: Float, U: NumCast>(x: U) -> T {
+    T::from(x).expect("value must be representable as a float")
 }
 
-fn sum_squares(template: &GrayImage) -> f32 {
-    template.iter().map(|p| *p as f32 * *p as f32).sum()
+fn sum_squares<T: Float>(template: &GrayImage, ignore_value: Option<u8>) -> T {
+    template
+        .iter()
+        .filter(|p| ignore_value != Some(**p))
+        .map(|p| to_float::<T, _>(*p).powi(2))
+        .fold(T::zero(), |acc, x| acc + x)
 }
 
-/// Returns the square root of the product of the sum of squares of
-/// pixel intensities in template and the provided region of image.
-fn normalization_term(
+/// Returns the product of the sum of squares of pixel intensities
+/// in template and the provided region of image.
+fn squared_normalization_term<T: Float>(
     image_squared_integral: &Image<Luma<u64>>,
-    template_squared_sum: f32,
+    template_squared_sum: T,
     region: Rect,
-) -> f32 {
-    let image_sum = sum_image_pixels(
-        image_squared_integral,
-        region.left() as u32,
-        region.top() as u32,
-        region.right() as u32,
-        region.bottom() as u32,
-    )[0] as f32;
-    (image_sum * template_squared_sum).sqrt()
+) -> T {
+    let image_sum: T = to_float(
+        sum_image_pixels(
+            image_squared_integral,
+            region.left() as u32,
+            region.top() as u32,
+            region.right() as u32,
+            region.bottom() as u32,
+        )[0],
+    );
+    image_sum * template_squared_sum
+}
+
+/// Returns the locations and scores of all entries in a `score_map` computed by
+/// [`match_template`] which are at least as good a match as `threshold`.
+///
+/// For the `SumOfSquaredErrors` methods smaller scores are better, so this returns
+/// all locations with score less than or equal to `threshold`. For the `CrossCorrelation`
+/// methods larger scores are better, so this returns all locations with score greater
+/// than or equal to `threshold`. Matches are returned in row-major order.
+///
+/// [`match_template`]: fn.match_template.html
+pub fn threshold_matches(
+    score_map: &Image<Luma<f32>>,
+    method: MatchTemplateMethod,
+    threshold: f32,
+) -> Vec<(u32, u32, f32)> {
+    use MatchTemplateMethod::*;
+
+    let is_match = |score: f32| match method {
+        SumOfSquaredErrors | SumOfSquaredErrorsNormalized => score <= threshold,
+        CrossCorrelation | CrossCorrelationNormalized => score >= threshold,
+    };
+
+    score_map
+        .enumerate_pixels()
+        .filter(|(_, _, p)| is_match(p[0]))
+        .map(|(x, y, p)| (x, y, p[0]))
+        .collect()
+}
+
+/// Finds all instances of `template` in `image`, returning the location of the top left
+/// corner of each instance and its score, best match first.
+///
+/// Scores are computed by [`match_template`], and locations which are not at least as good
+/// a match as `score_threshold` are discarded, as for [`threshold_matches`]. Non-maximum
+/// suppression is then applied greedily: the best remaining match is kept, and all other
+/// matches within `min_distance` of it in both x and y are discarded. Matches with equal
+/// scores are considered in row-major order.
+///
+/// # Panics
+///
+/// If either dimension of `template` is not strictly less than the corresponding dimension
+/// of `image`.
+///
+/// # Examples
+/// ```
+/// # extern crate image;
+/// # #[macro_use]
+/// # extern crate imageproc;
+/// # fn main() {
+/// use imageproc::template_matching::{find_template_instances, MatchTemplateMethod};
+///
+/// let image = gray_image!(
+///     0, 0, 0, 0, 0, 0;
+///     0, 9, 0, 0, 0, 0;
+///     0, 0, 0, 0, 9, 0;
+///     0, 0, 0, 0, 0, 0);
+/// let template = gray_image!(
+///     9, 0;
+///     0, 0);
+///
+/// let instances = find_template_instances(
+///     &image,
+///     &template,
+///     MatchTemplateMethod::SumOfSquaredErrors,
+///     0.0,
+///     1,
+/// );
+/// assert_eq!(instances, vec![(1, 1, 0.0), (4, 2, 0.0)]);
+/// # }
+/// ```
+///
+/// [`match_template`]: fn.match_template.html
+/// [`threshold_matches`]: fn.threshold_matches.html
+pub fn find_template_instances(
+    image: &GrayImage,
+    template: &GrayImage,
+    method: MatchTemplateMethod,
+    score_threshold: f32,
+    min_distance: u32,
+) -> Vec<(u32, u32, f32)> {
+    use MatchTemplateMethod::*;
+
+    let score_map = match_template(image, template, method);
+    let mut matches = threshold_matches(&score_map, method, score_threshold);
+    matches.sort_by(|a, b| {
+        let ordering = a.2.partial_cmp(&b.2).unwrap_or(Ordering::Equal);
+        match method {
+            SumOfSquaredErrors | SumOfSquaredErrorsNormalized => ordering,
+            CrossCorrelation | CrossCorrelationNormalized => ordering.reverse(),
+        }
+    });
+
+    let mut instances: Vec<(u32, u32, f32)> = Vec::new();
+    for m in matches {
+        let is_suppressed = instances.iter().any(|i| {
+            (i.0 as i64 - m.0 as i64).abs() <= min_distance as i64
+                && (i.1 as i64 - m.1 as i64).abs() <= min_distance as i64
+        });
+        if !is_suppressed {
+            instances.push(m);
+        }
+    }
+
+    instances
+}
+
+/// Rescales a `score_map` computed by [`match_template`] to `0..=255` for display.
+///
+/// Scores are mapped linearly using the smallest and largest values in `score_map`, so
+/// that the best match is brightest and the worst match is darkest. For the
+/// `SumOfSquaredErrors` methods this means the scale is inverted. If all scores are equal
+/// then every pixel is set to 255.
+///
+/// [`match_template`]: fn.match_template.html
+pub fn normalize_to_u8(score_map: &Image<Luma<f32>>, method: MatchTemplateMethod) -> GrayImage {
+    use MatchTemplateMethod::*;
+
+    let (width, height) = score_map.dimensions();
+    if width == 0 || height == 0 {
+        return GrayImage::new(width, height);
+    }
+
+    let extremes = find_extremes(score_map);
+    let (min, max) = (extremes.min_value, extremes.max_value);
+    let range = max - min;
+
+    GrayImage::from_fn(width, height, |x, y| {
+        if range <= 0.0 {
+            return Luma([255]);
+        }
+        let score = score_map.get_pixel(x, y)[0];
+        let quality = match method {
+            SumOfSquaredErrors | SumOfSquaredErrorsNormalized => (max - score) / range,
+            CrossCorrelation | CrossCorrelationNormalized => (score - min) / range,
+        };
+        Luma([(quality * 255.0).round() as u8])
+    })
 }
 
 /// The largest and smallest values in an image,
@@ -145,7 +983,60 @@ pub struct Extremes<T> {
 
 /// Finds the largest and smallest values in an image and their locations.
 /// If there are multiple such values then the lexicographically smallest is returned.
+///
+/// Use [`find_extremes_with`] to choose a different location for ties.
+///
+/// [`find_extremes_with`]: fn.find_extremes_with.html
 pub fn find_extremes<T>(image: &Image<Luma<T>>) -> Extremes<T>
+where
+    T: Primitive + 'static,
+{
+    find_extremes_with(image, TieBreak::default())
+}
+
+/// Which location [`find_extremes_with`] reports when several pixels share
+/// the largest or smallest value.
+///
+/// [`find_extremes_with`]: fn.find_extremes_with.html
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum TieBreak {
+    /// The first tied pixel in row-major order. This is the default.
+    #[default]
+    First,
+    /// The last tied pixel in row-major order.
+    Last,
+    /// The mean location of all tied pixels, rounded to the nearest pixel.
+    /// This need not be the location of one of the tied pixels,
+    /// e.g. if they form a ring.
+    Centroid,
+}
+
+/// Finds the largest and smallest values in an image and their locations,
+/// using `tie` to choose the location when a value occurs more than once.
+///
+/// # Panics
+///
+/// If `image` is empty.
+///
+/// # Examples
+/// ```
+/// # extern crate image;
+/// # #[macro_use]
+/// # extern crate imageproc;
+/// # fn main() {
+/// use imageproc::template_matching::{find_extremes_with, TieBreak};
+///
+/// let image = gray_image!(
+///     1, 9, 9, 9;
+///     1, 5, 5, 2);
+///
+/// assert_eq!(find_extremes_with(&image, TieBreak::First).max_value_location, (1, 0));
+/// assert_eq!(find_extremes_with(&image, TieBreak::Last).max_value_location, (3, 0));
+/// assert_eq!(find_extremes_with(&image, TieBreak::Centroid).max_value_location, (2, 0));
+/// assert_eq!(find_extremes_with(&image, TieBreak::Centroid).min_value_location, (0, 1));
+/// # }
+/// ```
+pub fn find_extremes_with<T>(image: &Image<Luma<T>>, tie: TieBreak) -> Extremes<T>
 where
     T: Primitive + 'static,
 {
@@ -154,38 +1045,251 @@ where
         "image must be non-empty"
     );
 
-    let mut min_value = image.get_pixel(0, 0)[0];
-    let mut max_value = image.get_pixel(0, 0)[0];
-
-    let mut min_value_location = (0, 0);
-    let mut max_value_location = (0, 0);
+    let mut min = TiedLocations::new(image.get_pixel(0, 0)[0]);
+    let mut max = TiedLocations::new(image.get_pixel(0, 0)[0]);
 
     for (x, y, p) in image.enumerate_pixels() {
-        if p[0] < min_value {
-            min_value = p[0];
-            min_value_location = (x, y);
+        if p[0] < min.value {
+            min = TiedLocations::new(p[0]);
+        }
+        if p[0] == min.value {
+            min.add(x, y);
         }
-        if p[0] > max_value {
-            max_value = p[0];
-            max_value_location = (x, y);
+        if p[0] > max.value {
+            max = TiedLocations::new(p[0]);
+        }
+        if p[0] == max.value {
+            max.add(x, y);
         }
     }
 
     Extremes {
-        max_value,
-        min_value,
-        max_value_location,
-        min_value_location,
+        max_value: max.value,
+        min_value: min.value,
+        max_value_location: max.location(tie),
+        min_value_location: min.location(tie),
     }
 }
 
+/// The locations of all pixels found so far with a given value.
+struct TiedLocations<T> {
+    value: T,
+    first: (u32, u32),
+    last: (u32, u32),
+    sum: (u64, u64),
+    count: u64,
+}
+
+impl<T> TiedLocations<T> {
+    fn new(value: T) -> Self {
+        TiedLocations {
+            value,
+            first: (0, 0),
+            last: (0, 0),
+            sum: (0, 0),
+            count: 0,
+        }
+    }
+
+    fn add(&mut self, x: u32, y: u32) {
+        if self.count == 0 {
+            self.first = (x, y);
+        }
+        self.last = (x, y);
+        self.sum = (self.sum.0 + x as u64, self.sum.1 + y as u64);
+        self.count += 1;
+    }
+
+    fn location(&self, tie: TieBreak) -> (u32, u32) {
+        match tie {
+            TieBreak::First => self.first,
+            TieBreak::Last => self.last,
+            TieBreak::Centroid => {
+                let mean = |sum: u64| ((sum + self.count / 2) / self.count) as u32;
+                (mean(self.sum.0), mean(self.sum.1))
+            }
+        }
+    }
+}
+
+/// Returns the locations and values of all local maxima in `image` with value at least
+/// `min_value`, in row-major order.
+///
+/// A pixel is a local maximum if its value is strictly greater than that of every other pixel
+/// within `min_distance` of it in both x and y, i.e. in the square of side `2 * min_distance + 1`
+/// centred on it, clipped to the image bounds. Pixels in a plateau of equal values are therefore
+/// never returned, even if the plateau is the global maximum, and no two returned pixels are
+/// within `min_distance` of each other.
+///
+/// Unlike [`find_extremes`], which finds a single global maximum, this finds every peak. To find
+/// the best matches in a score map computed by [`match_template`] using a `SumOfSquaredErrors`
+/// method, negate the scores first.
+///
+/// [`find_extremes`]: fn.find_extremes.html
+/// [`match_template`]: fn.match_template.html
+pub fn find_local_maxima(
+    image: &Image<Luma<f32>>,
+    min_value: f32,
+    min_distance: u32,
+) -> Vec<(u32, u32, f32)> {
+    let (width, height) = image.dimensions();
+    let mut maxima = Vec::new();
+
+    for (x, y, p) in image.enumerate_pixels() {
+        let value = p[0];
+        if value.is_nan() || value < min_value {
+            continue;
+        }
+
+        let x_range =
+            x.saturating_sub(min_distance)..=x.saturating_add(min_distance).min(width - 1);
+        let y_range =
+            y.saturating_sub(min_distance)..=y.saturating_add(min_distance).min(height - 1);
+
+        let is_maximum = y_range.clone().all(|ny| {
+            x_range
+                .clone()
+                .all(|nx| (nx == x && ny == y) || image.get_pixel(nx, ny)[0] < value)
+        });
+
+        if is_maximum {
+            maxima.push((x, y, value));
+        }
+    }
+
+    maxima
+}
+
+/// Finds the pixels of an image with the largest and smallest values of `key`,
+/// together with these values.
+///
+/// This generalises [`find_extremes`] to images with any pixel type. If there are
+/// multiple pixels with the largest or smallest key then the first in row-major
+/// order is returned.
+///
+/// # Panics
+///
+/// If `image` is empty.
+///
+/// # Examples
+/// ```
+/// # extern crate image;
+/// # #[macro_use]
+/// # extern crate imageproc;
+/// # fn main() {
+/// use imageproc::template_matching::find_extremes_by;
+///
+/// // A vector field, with the components of each vector stored in the first two channels.
+/// let field = rgb_image!(
+///     [3, 4, 0], [1, 1, 0];
+///     [0, 6, 0], [2, 0, 0]);
+///
+/// let extremes = find_extremes_by(&field, |p| {
+///     (p[0] as f32).hypot(p[1] as f32)
+/// });
+///
+/// assert_eq!(extremes.max_value, 6.0);
+/// assert_eq!(extremes.max_value_location, (0, 1));
+/// assert_eq!(extremes.min_value_location, (1, 0));
+/// # }
+/// ```
+///
+/// [`find_extremes`]: fn.find_extremes.html
+pub fn find_extremes_by<P, K, F>(image: &Image<P>, key: F) -> Extremes<K>
+where
+    P: Pixel + 'static,
+    K: PartialOrd + Copy,
+    F: Fn(&P) -> K,
+{
+    assert!(
+        image.width() > 0 && image.height() > 0,
+        "image must be non-empty"
+    );
+
+    let first = key(image.get_pixel(0, 0));
+    let mut extremes = Extremes {
+        max_value: first,
+        min_value: first,
+        max_value_location: (0, 0),
+        min_value_location: (0, 0),
+    };
+
+    for (x, y, p) in image.enumerate_pixels() {
+        let k = key(p);
+        if k < extremes.min_value {
+            extremes.min_value = k;
+            extremes.min_value_location = (x, y);
+        }
+        if k > extremes.max_value {
+            extremes.max_value = k;
+            extremes.max_value_location = (x, y);
+        }
+    }
+
+    extremes
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::map::map_colors;
     use crate::utils::gray_bench_image;
     use image::GrayImage;
     use test::{black_box, Bencher};
 
+    fn sum_of_absolute_differences(window: &WindowView<'_, Luma<u8>>, template: &GrayImage) -> f32 {
+        template
+            .enumerate_pixels()
+            .map(|(x, y, t)| window.get_pixel(x, y)[0].abs_diff(t[0]) as f32)
+            .sum()
+    }
+
+    #[test]
+    fn slide_window_custom_metric() {
+        let image = gray_image!(
+            1, 4, 2;
+            2, 1, 3;
+            3, 3, 4
+        );
+        let template = gray_image!(
+            1, 2;
+            3, 1
+        );
+
+        let actual = slide_window(&image, &template, sum_of_absolute_differences);
+        let expected = gray_image!(type: f32,
+            3.0, 7.0;
+            4.0, 4.0
+        );
+
+        assert_pixels_eq!(actual, expected);
+    }
+
+    #[test]
+    fn slide_window_matches_sum_of_squared_errors() {
+        let image = gray_bench_image(20, 15);
+        let template = gray_bench_image(4, 3);
+
+        let actual = slide_window(&image, &template, |window, template| {
+            template
+                .enumerate_pixels()
+                .map(|(x, y, t)| {
+                    let d = window.get_pixel(x, y)[0] as f32 - t[0] as f32;
+                    d * d
+                })
+                .sum()
+        });
+        let expected = match_template(&image, &template, MatchTemplateMethod::SumOfSquaredErrors);
+
+        assert_pixels_eq!(actual, expected);
+    }
+
+    #[test]
+    #[should_panic]
+    fn slide_window_panics_if_template_is_wider_than_image() {
+        let _ = slide_window(&GrayImage::new(5, 5), &GrayImage::new(6, 5), |_, _| 0.0);
+    }
+
     #[test]
     #[should_panic]
     fn match_template_panics_if_image_width_does_is_less_than_template_width() {
@@ -197,41 +1301,180 @@ mod tests {
     }
 
     #[test]
-    #[should_panic]
-    fn match_template_panics_if_image_height_is_less_than_template_height() {
-        let _ = match_template(
-            &GrayImage::new(5, 5),
-            &GrayImage::new(5, 6),
-            MatchTemplateMethod::SumOfSquaredErrors,
+    #[should_panic]
+    fn match_template_panics_if_image_height_is_less_than_template_height() {
+        let _ = match_template(
+            &GrayImage::new(5, 5),
+            &GrayImage::new(5, 6),
+            MatchTemplateMethod::SumOfSquaredErrors,
+        );
+    }
+
+    #[test]
+    fn match_template_handles_template_of_same_size_as_image() {
+        assert_pixels_eq!(
+            match_template(
+                &GrayImage::new(5, 5),
+                &GrayImage::new(5, 5),
+                MatchTemplateMethod::SumOfSquaredErrors
+            ),
+            gray_image!(type: f32, 0.0)
+        );
+    }
+
+    #[test]
+    fn match_template_normalization_handles_zero_norm() {
+        assert_pixels_eq!(
+            match_template(
+                &GrayImage::new(1, 1),
+                &GrayImage::new(1, 1),
+                MatchTemplateMethod::SumOfSquaredErrorsNormalized
+            ),
+            gray_image!(type: f32, 0.0)
+        );
+    }
+
+    #[test]
+    fn match_template_padded_matches_match_template_in_interior() {
+        let image = gray_image!(
+            1, 4, 2, 6;
+            2, 1, 3, 0;
+            3, 3, 4, 7
+        );
+        let template = gray_image!(
+            1, 2;
+            3, 4
+        );
+
+        for &border in &[BorderMode::Constant(Luma([5])), BorderMode::Reflect] {
+            let padded = match_template_padded(
+                &image,
+                &template,
+                MatchTemplateMethod::SumOfSquaredErrorsNormalized,
+                border,
+            );
+            assert_eq!(padded.dimensions(), (5, 4));
+            let interior = image::imageops::crop_imm(&padded, 1, 1, 3, 2).to_image();
+            assert_pixels_eq!(
+                interior,
+                match_template(
+                    &image,
+                    &template,
+                    MatchTemplateMethod::SumOfSquaredErrorsNormalized
+                )
+            );
+        }
+
+        // With replicated borders, the template placed with its top left corner at (-1, -1)
+        // covers the top left pixel of the image and three copies of it.
+        let replicated = match_template_padded(
+            &image,
+            &template,
+            MatchTemplateMethod::SumOfSquaredErrors,
+            BorderMode::Replicate,
+        );
+        assert_eq!(replicated.get_pixel(0, 0)[0], 0.0 + 1.0 + 4.0 + 9.0);
+    }
+
+    #[test]
+    fn match_template_padded_empty_image() {
+        let method = MatchTemplateMethod::CrossCorrelation;
+        let zero = BorderMode::Constant(Luma([0]));
+
+        let empty = GrayImage::new(0, 3);
+        let scores = match_template_padded(&empty, &GrayImage::new(1, 2), method, zero);
+        assert_eq!(scores.dimensions(), (0, 4));
+        let scores = match_template_padded(&empty, &GrayImage::new(1, 1), method, zero);
+        assert_eq!(scores.dimensions(), (0, 3));
+        let scores = match_template_padded(&empty, &GrayImage::new(2, 1), method, zero);
+        assert_pixels_eq!(scores, Image::from_pixel(1, 3, Luma([0f32])));
+    }
+
+    #[test]
+    fn match_template_sum_of_squared_errors() {
+        let image = gray_image!(
+            1, 4, 2;
+            2, 1, 3;
+            3, 3, 4
+        );
+        let template = gray_image!(
+            1, 2;
+            3, 4
+        );
+
+        let actual = match_template(&image, &template, MatchTemplateMethod::SumOfSquaredErrors);
+        let expected = gray_image!(type: f32,
+            14.0, 14.0;
+            3.0, 1.0
+        );
+
+        assert_pixels_eq!(actual, expected);
+    }
+
+    #[test]
+    fn score_positions_matches_match_template() {
+        let image = gray_image!(
+            1, 4, 2, 9;
+            2, 1, 3, 0;
+            3, 3, 4, 7
+        );
+        let template = gray_image!(
+            1, 2;
+            3, 4
         );
+        let positions = [(2, 1), (0, 0), (1, 0), (2, 1)];
+
+        for &method in &[
+            MatchTemplateMethod::SumOfSquaredErrors,
+            MatchTemplateMethod::SumOfSquaredErrorsNormalized,
+            MatchTemplateMethod::CrossCorrelation,
+            MatchTemplateMethod::CrossCorrelationNormalized,
+        ] {
+            let dense = match_template(&image, &template, method);
+            let expected: Vec<f32> = positions
+                .iter()
+                .map(|&(x, y)| dense.get_pixel(x, y)[0])
+                .collect();
+            assert_eq!(
+                score_positions(&image, &template, &positions, method),
+                expected
+            );
+        }
     }
 
     #[test]
-    fn match_template_handles_template_of_same_size_as_image() {
-        assert_pixels_eq!(
-            match_template(
-                &GrayImage::new(5, 5),
-                &GrayImage::new(5, 5),
-                MatchTemplateMethod::SumOfSquaredErrors
-            ),
-            gray_image!(type: f32, 0.0)
-        );
+    fn score_positions_matches_match_template_for_large_template() {
+        let image = gray_bench_image(40, 30);
+        let template = gray_bench_image(25, 20);
+        let positions = [(0, 0), (15, 10), (7, 3)];
+
+        for &method in &[
+            MatchTemplateMethod::SumOfSquaredErrorsNormalized,
+            MatchTemplateMethod::CrossCorrelationNormalized,
+        ] {
+            let dense = match_template(&image, &template, method);
+            for (&(x, y), score) in positions
+                .iter()
+                .zip(score_positions(&image, &template, &positions, method))
+            {
+                assert_eq!(score, dense.get_pixel(x, y)[0]);
+            }
+        }
     }
 
     #[test]
-    fn match_template_normalization_handles_zero_norm() {
-        assert_pixels_eq!(
-            match_template(
-                &GrayImage::new(1, 1),
-                &GrayImage::new(1, 1),
-                MatchTemplateMethod::SumOfSquaredErrorsNormalized
-            ),
-            gray_image!(type: f32, 0.0)
+    #[should_panic]
+    fn score_positions_panics_if_template_outside_image() {
+        let _ = score_positions(
+            &GrayImage::new(5, 5),
+            &GrayImage::new(2, 2),
+            &[(4, 0)],
+            MatchTemplateMethod::SumOfSquaredErrors,
         );
     }
 
     #[test]
-    fn match_template_sum_of_squared_errors() {
+    fn match_template_sse_int_matches_match_template() {
         let image = gray_image!(
             1, 4, 2;
             2, 1, 3;
@@ -242,13 +1485,24 @@ mod tests {
             3, 4
         );
 
-        let actual = match_template(&image, &template, MatchTemplateMethod::SumOfSquaredErrors);
-        let expected = gray_image!(type: f32,
-            14.0, 14.0;
-            3.0, 1.0
+        let exact = match_template_sse_int(&image, &template);
+        let expected = match_template(&image, &template, MatchTemplateMethod::SumOfSquaredErrors);
+        assert_pixels_eq!(map_colors(&exact, |p| Luma([p[0] as f32])), expected);
+    }
+
+    #[test]
+    fn match_template_sse_int_sums_over_channels() {
+        let image = rgb_image!(
+            [1, 2, 3], [10, 20, 30];
+            [4, 4, 4], [0, 0, 0]
         );
+        let template = rgb_image!([1, 3, 5]);
 
-        assert_pixels_eq!(actual, expected);
+        let expected = gray_image!(type: u64,
+            5, 81 + 289 + 625;
+            9 + 1 + 1, 1 + 9 + 25
+        );
+        assert_pixels_eq!(match_template_sse_int(&image, &template), expected);
     }
 
     #[test]
@@ -324,6 +1578,269 @@ mod tests {
         assert_pixels_eq!(actual, expected);
     }
 
+    #[test]
+    fn match_template_f64_matches_f32() {
+        let image = gray_image!(
+            1, 4, 2;
+            2, 1, 3;
+            3, 3, 4
+        );
+        let template = gray_image!(
+            1, 2;
+            3, 4
+        );
+
+        let actual = match_template_f64(
+            &image,
+            &template,
+            MatchTemplateMethod::CrossCorrelationNormalized,
+        );
+        let tss = 30f64;
+        let expected = gray_image!(type: f64,
+            19.0 / (22.0 * tss).sqrt(), 23.0 / (30.0 * tss).sqrt();
+            25.0 / (23.0 * tss).sqrt(), 32.0 / (35.0 * tss).sqrt()
+        );
+
+        assert_pixels_eq!(actual, expected);
+        assert_eq!(find_extremes(&actual).max_value_location, (1, 1));
+
+        let image = gray_bench_image(20, 15);
+        let template = gray_bench_image(4, 3);
+        for &method in &[
+            MatchTemplateMethod::SumOfSquaredErrors,
+            MatchTemplateMethod::SumOfSquaredErrorsNormalized,
+            MatchTemplateMethod::CrossCorrelation,
+            MatchTemplateMethod::CrossCorrelationNormalized,
+        ] {
+            let single = match_template(&image, &template, method);
+            let double = match_template_f64(&image, &template, method);
+            assert_dimensions_match!(single, double);
+            for (s, d) in single.pixels().zip(double.pixels()) {
+                let (s, d) = (s[0] as f64, d[0]);
+                assert!(
+                    (s - d).abs() <= 1e-5 * d.abs().max(1.0),
+                    "{:?}: f32 score {} differs from f64 score {}",
+                    method,
+                    s,
+                    d
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_threshold_matches() {
+        let scores = gray_image!(type: f32,
+            1.0, 5.0;
+            3.0, 2.0
+        );
+
+        assert_eq!(
+            threshold_matches(&scores, MatchTemplateMethod::SumOfSquaredErrors, 2.0),
+            vec![(0, 0, 1.0), (1, 1, 2.0)]
+        );
+        assert_eq!(
+            threshold_matches(&scores, MatchTemplateMethod::CrossCorrelation, 3.0),
+            vec![(1, 0, 5.0), (0, 1, 3.0)]
+        );
+    }
+
+    #[test]
+    fn test_normalize_to_u8() {
+        let scores = gray_image!(type: f32,
+            1.0, 5.0;
+            3.0, 2.0
+        );
+
+        let expected = gray_image!(
+            255,   0;
+            128, 191
+        );
+        assert_pixels_eq!(
+            normalize_to_u8(&scores, MatchTemplateMethod::SumOfSquaredErrors),
+            expected
+        );
+
+        let expected = gray_image!(
+              0, 255;
+            128,  64
+        );
+        assert_pixels_eq!(
+            normalize_to_u8(&scores, MatchTemplateMethod::CrossCorrelation),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_normalize_to_u8_constant_scores() {
+        let scores = gray_image!(type: f32, 2.0, 2.0; 2.0, 2.0);
+        let expected = gray_image!(255, 255; 255, 255);
+        assert_pixels_eq!(
+            normalize_to_u8(&scores, MatchTemplateMethod::CrossCorrelationNormalized),
+            expected
+        );
+    }
+
+    #[test]
+    fn match_template_strided_samples_full_score_map() {
+        let image = gray_bench_image(23, 17);
+        let template = gray_bench_image(4, 5);
+
+        for &method in &[
+            MatchTemplateMethod::SumOfSquaredErrorsNormalized,
+            MatchTemplateMethod::CrossCorrelation,
+        ] {
+            let full = match_template(&image, &template, method);
+            for &step in &[1, 2, 3, 20, 100] {
+                let strided = match_template_strided(&image, &template, method, step);
+                let expected = Image::from_fn(
+                    (full.width() + step - 1) / step,
+                    (full.height() + step - 1) / step,
+                    |x, y| *full.get_pixel(x * step, y * step),
+                );
+                assert_pixels_eq!(strided, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn match_template_ignoring_value_sum_of_squared_errors() {
+        let image = gray_image!(
+            1, 4, 2;
+            2, 1, 3;
+            3, 3, 4
+        );
+        let template = gray_image!(
+            1, 255;
+            3, 1
+        );
+
+        // The top right template pixel is ignored.
+        let expected = gray_image!(type: f32,
+            1.0, 17.0;
+            5.0, 9.0
+        );
+
+        let actual = match_template_ignoring_value(
+            &image,
+            &template,
+            MatchTemplateMethod::SumOfSquaredErrors,
+            255,
+        );
+        assert_pixels_eq!(actual, expected);
+    }
+
+    #[test]
+    fn match_template_ignoring_value_normalized_matches_cropped_template() {
+        let image = gray_bench_image(12, 9);
+        let template = gray_image!(
+            3, 7, 1;
+            9, 2, 5;
+            255, 255, 255
+        );
+        let cropped = gray_image!(
+            3, 7, 1;
+            9, 2, 5
+        );
+
+        for &method in &[
+            MatchTemplateMethod::SumOfSquaredErrorsNormalized,
+            MatchTemplateMethod::CrossCorrelationNormalized,
+        ] {
+            let ignoring = match_template_ignoring_value(&image, &template, method, 255);
+            let expected = match_template(&image, &cropped, method);
+            for (x, y, p) in ignoring.enumerate_pixels() {
+                assert_approx_eq!(p[0], expected.get_pixel(x, y)[0], 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn match_template_with_norm_cross_correlation_normalized() {
+        let image = gray_image!(
+            1, 4, 2;
+            2, 1, 3;
+            3, 3, 4
+        );
+        let template = gray_image!(
+            1, 2;
+            3, 4
+        );
+
+        let (scores, norms) = match_template_with_norm(
+            &image,
+            &template,
+            MatchTemplateMethod::CrossCorrelationNormalized,
+        );
+        assert_pixels_eq!(
+            scores,
+            match_template(
+                &image,
+                &template,
+                MatchTemplateMethod::CrossCorrelationNormalized
+            )
+        );
+
+        // The template sum of squares is 30.
+        let expected_norms = gray_image!(type: f32,
+            (22.0f32 * 30.0).sqrt(), (30.0f32 * 30.0).sqrt();
+            (23.0f32 * 30.0).sqrt(), (35.0f32 * 30.0).sqrt()
+        );
+        assert_pixels_eq_within!(norms, expected_norms, 1e-4);
+    }
+
+    #[test]
+    fn match_template_with_norm_unnormalized_method() {
+        let image = gray_bench_image(8, 7);
+        let template = gray_bench_image(3, 2);
+        let (scores, norms) =
+            match_template_with_norm(&image, &template, MatchTemplateMethod::SumOfSquaredErrors);
+        assert_pixels_eq!(
+            scores,
+            match_template(&image, &template, MatchTemplateMethod::SumOfSquaredErrors)
+        );
+        assert!(norms.pixels().all(|p| p[0] == 1.0));
+    }
+
+    #[test]
+    fn match_template_luma_rgb_image_gray_template() {
+        use image::{Rgb, RgbImage};
+
+        let gray = gray_bench_image(10, 8);
+        let rgb = RgbImage::from_fn(10, 8, |x, y| {
+            let v = gray.get_pixel(x, y)[0];
+            Rgb([v, v, v])
+        });
+        let template = gray_bench_image(3, 2);
+
+        for &method in &[
+            MatchTemplateMethod::SumOfSquaredErrors,
+            MatchTemplateMethod::CrossCorrelationNormalized,
+        ] {
+            assert_pixels_eq!(
+                match_template_luma(&rgb, &template, method),
+                match_template(&gray, &template, method)
+            );
+        }
+    }
+
+    #[test]
+    fn match_template_tiled_matches_untiled() {
+        let image = gray_bench_image(23, 17);
+        let template = gray_bench_image(4, 5);
+
+        for &method in &[
+            MatchTemplateMethod::SumOfSquaredErrors,
+            MatchTemplateMethod::CrossCorrelationNormalized,
+        ] {
+            let expected = match_template(&image, &template, method);
+            for &tile in &[(1, 1), (3, 7), (8, 8), (100, 100)] {
+                let actual = match_template_tiled(&image, &template, method, tile);
+                assert_pixels_eq!(actual, expected);
+            }
+        }
+    }
+
     macro_rules! bench_match_template {
         ($name:ident, image_size: $s:expr, template_size: $t:expr, method: $m:expr) => {
             #[bench]
@@ -375,6 +1892,106 @@ mod tests {
         template_size: 16,
         method: MatchTemplateMethod::SumOfSquaredErrorsNormalized);
 
+    #[test]
+    fn test_match_templates_matches_match_template() {
+        let image = gray_image!(
+            1, 4, 2, 7;
+            9, 3, 3, 1;
+            0, 5, 8, 2;
+            6, 2, 4, 4);
+        let small = gray_image!(
+            3, 1;
+            2, 5);
+        let tall = gray_image!(
+            2;
+            9;
+            1);
+
+        for &method in &[
+            MatchTemplateMethod::SumOfSquaredErrors,
+            MatchTemplateMethod::SumOfSquaredErrorsNormalized,
+            MatchTemplateMethod::CrossCorrelation,
+            MatchTemplateMethod::CrossCorrelationNormalized,
+        ] {
+            let results = match_templates(&image, &[&small, &tall], method);
+            assert_eq!(results.len(), 2);
+            assert_pixels_eq!(results[0], match_template(&image, &small, method));
+            assert_pixels_eq!(results[1], match_template(&image, &tall, method));
+        }
+    }
+
+    #[test]
+    fn test_match_template_ranked() {
+        let image = gray_image!(
+            1, 4, 2, 7;
+            9, 3, 3, 1;
+            0, 5, 8, 2;
+            6, 2, 4, 4);
+        let template = gray_image!(
+            3, 1;
+            2, 5);
+
+        for &method in &[
+            MatchTemplateMethod::SumOfSquaredErrors,
+            MatchTemplateMethod::SumOfSquaredErrorsNormalized,
+            MatchTemplateMethod::CrossCorrelation,
+            MatchTemplateMethod::CrossCorrelationNormalized,
+        ] {
+            let scores = match_template(&image, &template, method);
+            let ranked = match_template_ranked(&image, &template, method);
+            let expected = match method {
+                MatchTemplateMethod::SumOfSquaredErrorsNormalized
+                | MatchTemplateMethod::CrossCorrelationNormalized => {
+                    map_colors(&scores, |p| Luma([p[0] * p[0]]))
+                }
+                _ => scores.clone(),
+            };
+            assert_pixels_eq_within!(ranked, expected, 1e-5);
+            assert_eq!(
+                find_extremes(&ranked).max_value_location,
+                find_extremes(&scores).max_value_location
+            );
+        }
+    }
+
+    #[test]
+    fn test_find_template_instances() {
+        let template = gray_image!(
+            9, 8;
+            7, 6
+        );
+        let mut image = GrayImage::new(12, 10);
+        for &(x, y, delta) in &[(1, 1, 0), (7, 2, 1), (8, 2, 2), (3, 6, 0)] {
+            for (tx, ty, p) in template.enumerate_pixels() {
+                image.put_pixel(x + tx, y + ty, Luma([p[0] - delta]));
+            }
+        }
+
+        // The exact copies score 0. The copy at (7, 2) is partly overwritten by the one
+        // at (8, 2), but scores 4 whereas that at (8, 2) scores 16, so is suppressed.
+        let instances = find_template_instances(
+            &image,
+            &template,
+            MatchTemplateMethod::SumOfSquaredErrors,
+            20.0,
+            2,
+        );
+        let locations: Vec<(u32, u32)> = instances.iter().map(|i| (i.0, i.1)).collect();
+        assert_eq!(locations, vec![(1, 1), (3, 6), (7, 2)]);
+        assert_eq!(instances[2].2, 4.0);
+        assert_eq!(instances[0].2, 0.0);
+
+        let correlated = find_template_instances(
+            &image,
+            &template,
+            MatchTemplateMethod::CrossCorrelationNormalized,
+            0.999,
+            2,
+        );
+        assert_eq!(correlated.len(), 3);
+        assert!(correlated.windows(2).all(|w| w[0].2 >= w[1].2));
+    }
+
     #[test]
     fn test_find_extremes() {
         let image = gray_image!(
@@ -391,4 +2008,79 @@ mod tests {
 
         assert_eq!(find_extremes(&image), expected);
     }
+
+    #[test]
+    fn test_find_extremes_with_tie_breaks() {
+        let image = gray_image!(
+            3, 0, 3, 1;
+            1, 0, 2, 3;
+            3, 1, 1, 0
+        );
+
+        let first = find_extremes_with(&image, TieBreak::First);
+        assert_eq!(first, find_extremes(&image));
+        assert_eq!(first.max_value_location, (0, 0));
+        assert_eq!(first.min_value_location, (1, 0));
+
+        let last = find_extremes_with(&image, TieBreak::Last);
+        assert_eq!((last.max_value, last.min_value), (3, 0));
+        assert_eq!(last.max_value_location, (0, 2));
+        assert_eq!(last.min_value_location, (3, 2));
+
+        // Maxima at (0, 0), (2, 0), (3, 1), (0, 2) have mean (1.25, 0.75).
+        // Minima at (1, 0), (1, 1), (3, 2) have mean (1.67, 1).
+        let centroid = find_extremes_with(&image, TieBreak::Centroid);
+        assert_eq!(centroid.max_value_location, (1, 1));
+        assert_eq!(centroid.min_value_location, (2, 1));
+    }
+
+    #[test]
+    fn test_find_local_maxima() {
+        let image = gray_image!(type: f32,
+            1.0, 0.0, 0.0, 0.0, 0.5;
+            0.0, 0.0, 0.0, 3.0, 0.0;
+            2.0, 0.0, 0.0, 0.0, 0.0;
+            0.0, 0.0, 4.0, 4.0, 0.0);
+
+        assert_eq!(
+            find_local_maxima(&image, 0.5, 1),
+            vec![(0, 0, 1.0), (3, 1, 3.0), (0, 2, 2.0)]
+        );
+        assert_eq!(find_local_maxima(&image, 2.5, 1), vec![(3, 1, 3.0)]);
+        // Every peak is within distance 2 of a larger value or the plateau of 4.0s.
+        assert_eq!(find_local_maxima(&image, 0.0, 2), vec![]);
+        assert_eq!(
+            find_local_maxima(&image, 0.0, 0).len(),
+            image.pixels().count()
+        );
+    }
+
+    #[test]
+    fn test_find_local_maxima_drops_plateaus() {
+        let horizontal = gray_image!(type: f32,
+            0.0, 1.0, 0.0, 0.0;
+            0.0, 0.0, 5.0, 5.0;
+            2.0, 0.0, 0.0, 0.0);
+        assert_eq!(find_local_maxima(&horizontal, 0.0, 1), vec![(0, 2, 2.0)]);
+
+        let vertical = gray_image!(type: f32,
+            0.0, 5.0, 0.0;
+            0.0, 5.0, 0.0;
+            0.0, 0.0, 1.0);
+        assert_eq!(find_local_maxima(&vertical, 0.0, 1), vec![]);
+    }
+
+    #[test]
+    fn test_find_extremes_by_matches_find_extremes() {
+        let image = gray_image!(
+            10,  7,  8,  1;
+             9, 15,  4,  1
+        );
+        assert_eq!(find_extremes_by(&image, |p| p[0]), find_extremes(&image));
+
+        let negated = find_extremes_by(&image, |p| -(p[0] as i32));
+        assert_eq!(negated.max_value, -1);
+        assert_eq!(negated.max_value_location, (3, 0));
+        assert_eq!(negated.min_value_location, (1, 1));
+    }
 }